@@ -0,0 +1,61 @@
+// Benchmarks parsing and both parts of every day on the cached puzzle inputs.
+// Days without a cached input (see `aoc run`) are skipped, e.g. `cargo bench -- day15`.
+
+use std::fs;
+
+use advent22::{input::Config, *};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_day<S: Solution>(c: &mut Criterion, config: &Config, day: u8) {
+    let input = match fs::read_to_string(config.cache_path(day)) {
+        Ok(input) => input,
+        Err(_) => {
+            eprintln!("Skipping day {}, its input is not cached", day);
+            return;
+        }
+    };
+
+    let parsed = match S::parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Skipping day {}, its input is invalid: {}", day, e);
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group(format!("day{}", day));
+    // Some days take seconds per iteration, keep the number of samples at the minimum
+    group.sample_size(10);
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    let config = Config::from_env().expect("Invalid configuration");
+
+    bench_day::<day1::Day1>(c, &config, 1);
+    bench_day::<day2::Day2>(c, &config, 2);
+    bench_day::<day3::Day3>(c, &config, 3);
+    bench_day::<day4::Day4>(c, &config, 4);
+    bench_day::<day5::Day5>(c, &config, 5);
+    bench_day::<day6::Day6>(c, &config, 6);
+    bench_day::<day7::Day7>(c, &config, 7);
+    bench_day::<day8::Day8>(c, &config, 8);
+    bench_day::<day9::Day9>(c, &config, 9);
+    bench_day::<day10::Day10>(c, &config, 10);
+    bench_day::<day11::Day11>(c, &config, 11);
+    bench_day::<day12::Day12>(c, &config, 12);
+    bench_day::<day13::Day13>(c, &config, 13);
+    bench_day::<day14::Day14>(c, &config, 14);
+    bench_day::<day15::Day15>(c, &config, 15);
+    bench_day::<day16::Day16>(c, &config, 16);
+    bench_day::<day17::Day17>(c, &config, 17);
+    bench_day::<day18::Day18>(c, &config, 18);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,204 @@
+//! Known-correct answers, stored in `answers.toml`:
+//!
+//! ```toml
+//! [day1]
+//! part1 = "71924"
+//! part2 = "210406"
+//! ```
+
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use toml::Value;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Errors while reading or writing the answers file.
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(e) => write!(f, "Could not access the answers file: {}", e),
+            AnswersError::Toml(e) => write!(f, "Could not parse the answers file: {}", e),
+            AnswersError::Invalid(msg) => write!(f, "Invalid answers file: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl From<io::Error> for AnswersError {
+    fn from(e: io::Error) -> Self {
+        AnswersError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for AnswersError {
+    fn from(e: toml::de::Error) -> Self {
+        AnswersError::Toml(e)
+    }
+}
+
+/// Result of comparing a computed answer with the recorded one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail => write!(f, "FAIL"),
+            Check::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+/// Recorded answers per day and part.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    /// Loads the answers from a file, a missing file has no answers.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Answers::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Answers, AnswersError> {
+        let value = content.parse::<Value>()?;
+        let mut answers = Answers::default();
+
+        let days = value
+            .as_table()
+            .ok_or_else(|| AnswersError::Invalid("expected a table".to_string()))?;
+
+        for (key, parts) in days {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| AnswersError::Invalid(format!("unknown day {}", key)))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| AnswersError::Invalid(format!("{} is not a table", key)))?;
+
+            for (key, answer) in parts {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(AnswersError::Invalid(format!("unknown part {}", key))),
+                };
+                // Numbers may be written without quotes
+                let answer = match answer {
+                    Value::String(s) => s.clone(),
+                    Value::Integer(n) => n.to_string(),
+                    _ => {
+                        return Err(AnswersError::Invalid(format!(
+                            "answer of day {} part {} is not a string",
+                            day, part
+                        )))
+                    }
+                };
+                answers.set(day, part, answer);
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Check {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Check::Pass,
+            Some(_) => Check::Fail,
+            None => Check::Missing,
+        }
+    }
+}
+
+// Written by hand to keep the days in numeric order
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut last_day = None;
+        for ((day, part), answer) in &self.answers {
+            if last_day != Some(*day) {
+                if last_day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{}]", day)?;
+                last_day = Some(*day);
+            }
+            writeln!(f, "part{} = {}", part, Value::String(answer.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "[day1]
+part1 = \"71924\"
+part2 = \"210406\"
+
+[day5]
+part1 = \"CMZ\"
+
+[day10]
+part1 = \"13140\"
+part2 = \"##..\\n..##\"
+";
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(1, 1), Some("71924"));
+        assert_eq!(answers.get(5, 1), Some("CMZ"));
+        assert_eq!(answers.get(5, 2), None);
+        assert_eq!(answers.get(10, 2), Some("##..\n..##"));
+
+        assert_eq!(answers.check(1, 2, "210406"), Check::Pass);
+        assert_eq!(answers.check(1, 2, "210407"), Check::Fail);
+        assert_eq!(answers.check(2, 1, "15"), Check::Missing);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.to_string(), ANSWERS);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Answers::parse("[day1]\npart3 = \"1\"").is_err());
+        assert!(Answers::parse("[first]\npart1 = \"1\"").is_err());
+        assert!(Answers::parse("[day1]\npart1 = [1, 2]").is_err());
+        assert_eq!(Answers::parse("[day1]\npart1 = 12").unwrap().get(1, 1), Some("12"));
+    }
+}
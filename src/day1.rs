@@ -1,25 +1,245 @@
-mod input;
+//! Day 1: Calorie Counting
 
-fn group_by_elves(input: &str) -> Vec<Vec<u64>> {
-   input.lines().fold(vec![vec![]], |mut acc, line| {
-      if line.is_empty() {
-          acc.push(vec![]);
-      } else {
-          acc.last_mut().unwrap().push(line.parse::<u64>().unwrap());
-      }
-        acc
-  })
+use crate::Solution;
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+/// A line of the calorie list that is neither empty nor a number.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidLine {
+    /// 1-based line number.
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for InvalidLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {} is not a number: {:?}", self.line, self.text)
+    }
+}
+
+impl std::error::Error for InvalidLine {}
+
+/// Splits the calorie list into the items carried by each elf. A separator after the last elf
+/// does not start another one.
+pub fn group_by_elves(input: &str) -> Result<Vec<Vec<u64>>, InvalidLine> {
+    let mut elves = vec![vec![]];
+    for (i, line) in input.lines().enumerate() {
+        // Separators may hold whitespace, as the numbers are parsed trimmed
+        if line.trim().is_empty() {
+            elves.push(vec![]);
+        } else {
+            let calories = line.trim().parse::<u64>().map_err(|_| InvalidLine {
+                line: i + 1,
+                text: line.to_string(),
+            })?;
+            elves.last_mut().unwrap().push(calories);
+        }
+    }
+    if elves.len() > 1 && elves.last().is_some_and(Vec::is_empty) {
+        elves.pop();
+    }
+    Ok(elves)
+}
+
+/// Total calories carried by each elf, in the order of the list.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Calories {
+    totals: Vec<u64>,
+    empty: usize,
+}
+
+/// Summary of the calories carried by all elves.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    /// Elves without any items, e.g. from several empty lines in a row.
+    pub empty: usize,
+    /// Wider than the totals of the elves, so it cannot overflow.
+    pub total: u128,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl Calories {
+    pub fn parse(input: &str) -> Result<Calories, InvalidLine> {
+        let elves = group_by_elves(input)?;
+        Ok(Calories {
+            totals: elves.iter().map(|items| items.iter().sum()).collect(),
+            empty: elves.iter().filter(|items| items.is_empty()).count(),
+        })
+    }
+
+    /// Index and total of every elf.
+    pub fn totals(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.totals.iter().copied().enumerate()
+    }
+
+    /// The `k` elves carrying the most calories as index and total, most calories first.
+    /// Ties go to the elf that comes first.
+    ///
+    /// Only `k` elves are kept in a heap at a time, the totals are not sorted.
+    pub fn top_k(&self, k: usize) -> Vec<(usize, u64)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // A min-heap of the best elves so far, its top is the one to drop next
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for (index, total) in self.totals() {
+            heap.push(Reverse((total, Reverse(index))));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| (index, total))
+            .collect()
+    }
+
+    /// Sum of the calories of the `k` elves carrying the most.
+    pub fn top_k_sum(&self, k: usize) -> u64 {
+        self.top_k(k).iter().map(|(_, total)| total).sum()
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut sorted = self.totals.clone();
+        sorted.sort_unstable();
+        let elves = sorted.len();
+        let total = sorted.iter().map(|&total| total as u128).sum();
+
+        let median = match elves {
+            0 => 0.0,
+            n if n % 2 == 1 => sorted[n / 2] as f64,
+            n => (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0,
+        };
+
+        Stats {
+            elves,
+            empty: self.empty,
+            total,
+            mean: if elves == 0 { 0.0 } else { total as f64 / elves as f64 },
+            median,
+        }
+    }
+}
+
+/// Most calories carried by one elf, and by the top three elves combined.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Calories;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(Calories::parse(input)?)
+    }
+
+    fn part1(calories: &Self::Input<'_>) -> u64 {
+        calories.top_k_sum(1)
+    }
+
+    fn part2(calories: &Self::Input<'_>) -> u64 {
+        calories.top_k_sum(3)
+    }
 }
 
-pub fn main() {
-   let input_string: String = input::get_input(1);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(Day1::part1(&Day1::parse(INPUT).unwrap()), 24000);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(Day1::part2(&Day1::parse(INPUT).unwrap()), 45000);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let calories = Calories::parse(INPUT).unwrap();
+        assert_eq!(calories.top_k(0), vec![]);
+        assert_eq!(calories.top_k(2), vec![(3, 24000), (2, 11000)]);
+        assert_eq!(calories.top_k(10).len(), 5);
+
+        // Ties keep the elf that comes first
+        let calories = Calories::parse("5\n\n7\n\n3\n2\n\n7").unwrap();
+        assert_eq!(calories.top_k(2), vec![(1, 7), (3, 7)]);
+        assert_eq!(calories.top_k(3), vec![(1, 7), (3, 7), (0, 5)]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Calories::parse(INPUT).unwrap().stats();
+        assert_eq!(
+            stats,
+            Stats {
+                elves: 5,
+                empty: 0,
+                total: 55000,
+                mean: 11000.0,
+                median: 10000.0,
+            }
+        );
+
+        let stats = Calories::parse("1\n\n\n3\n\n4").unwrap().stats();
+        assert_eq!((stats.elves, stats.empty), (4, 1));
+        assert_eq!(stats.median, 2.0);
+    }
+
+    #[test]
+    fn test_stats_large_totals() {
+        let max = u64::MAX.to_string();
+        let stats = Calories::parse(&format!("{}\n\n{}", max, max)).unwrap().stats();
+        assert_eq!(stats.total, 2 * u64::MAX as u128);
+        assert_eq!(stats.median, u64::MAX as f64);
+    }
 
-   let grouped = group_by_elves(&input_string);
-   let mut sums: Vec<u64> = grouped.iter().map(|x| x.iter().sum()).collect();
-   sums.sort();
+    #[test]
+    fn test_trailing_separator() {
+        assert_eq!(group_by_elves("1000\n2000\n\n"), Ok(vec![vec![1000, 2000]]));
+        let stats = Calories::parse("1\n\n2\n\n").unwrap().stats();
+        assert_eq!((stats.elves, stats.empty), (2, 0));
+        // Only the last separator is dropped, elves without items before it still count
+        assert_eq!(group_by_elves("1\n\n\n"), Ok(vec![vec![1], vec![]]));
+    }
 
-   println!("Biggest sum ist: {}", sums.last().unwrap());
+    #[test]
+    fn test_whitespace_separator() {
+        assert_eq!(
+            group_by_elves("1000\n  \n2000\n\t\n 3000 "),
+            Ok(vec![vec![1000], vec![2000], vec![3000]])
+        );
+    }
 
-let sum_max3: u64 = sums.iter().rev().take(3).sum();
-   println!("Sum of the 3 biggest sums is: {}", sum_max3);
+    #[test]
+    fn test_invalid_line() {
+        assert_eq!(
+            Calories::parse("1000\n\n20x0\n"),
+            Err(InvalidLine {
+                line: 3,
+                text: "20x0".to_string()
+            })
+        );
+    }
 }
@@ -1,20 +1,27 @@
-mod input;
+//! Day 10: Cathode-Ray Tube
 
-struct Cpu {
-    register: i32,
-    cycle: i32,
-    history: Vec<i32>,
-    output: String,
+use crate::Solution;
+
+/// The handheld's CPU, drawing a pixel on the CRT every cycle.
+pub struct Cpu {
+    pub register: i32,
+    pub cycle: i32,
+    /// Value of the register during each cycle.
+    pub history: Vec<i32>,
+    /// The pixels drawn so far, one line per 40 cycles.
+    pub output: String,
     width: usize,
 }
 
-enum Instruction {
+/// An instruction of the CPU.
+#[derive(Clone, Copy)]
+pub enum Instruction {
     Addx(i32),
     Noop
 }
 
 impl Cpu {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Cpu {
             register: 1,
             cycle: 0,
@@ -40,7 +47,8 @@ impl Cpu {
         self.cycle += 1;
     }
 
-    fn execute(&mut self, instruction: Instruction) {
+    /// Runs an instruction, taking one cycle for `noop` and two cycles for `addx`.
+    pub fn execute(&mut self, instruction: Instruction) {
         match instruction {
             Instruction::Addx(x) => {
                 self.advance_cycle();
@@ -52,7 +60,14 @@ impl Cpu {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses the program into its instructions.
+pub fn parse_input(input: &str) -> Vec<Instruction> {
     input.lines().map(|line| {
         let parts = line.split_whitespace().collect::<Vec<&str>>();
         match parts[0] {
@@ -62,27 +77,43 @@ fn parse_input(input: &str) -> Vec<Instruction> {
         }
     }).collect()
 }
-fn main() {
-    let inp = input::get_input(10);
-    println!("Part 1: {}", part_1(parse_input(&inp)));
-    println!("Part 2:\n{}", part_2(parse_input(&inp)));
+
+/// Sum of the signal strengths during the 20th, 60th, ... cycle, and the image drawn on the CRT.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(instructions: &Self::Input<'_>) -> i32 {
+        part_1(instructions)
+    }
+
+    fn part2(instructions: &Self::Input<'_>) -> String {
+        part_2(instructions)
+    }
 }
 
-fn part_1(instructions: Vec<Instruction>) -> i32 {
+fn part_1(instructions: &[Instruction]) -> i32 {
     let mut cpu = Cpu::new();
     for instruction in instructions {
-        cpu.execute(instruction);
+        cpu.execute(*instruction);
     }
     cpu.history.iter().skip(19).step_by(40).enumerate().map(|(i, x)| {
         (i*40+20) as i32 * x
     }).sum()
 }
 
-fn part_2(instructions: Vec<Instruction>) -> String {
+fn part_2(instructions: &[Instruction]) -> String {
     let mut cpu = Cpu::new();
 
     for instruction in instructions {
-        cpu.execute(instruction);
+        cpu.execute(*instruction);
     }
     cpu.output
 }
@@ -90,19 +121,18 @@ fn part_2(instructions: Vec<Instruction>) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = include_str!("../test_input/day10.txt");
+    use crate::examples::load_example;
 
     #[test]
     fn test_part_1() {
-        let inp = parse_input(INPUT);
-        assert_eq!(part_1(inp), 13140);
+        let inp = parse_input(&load_example(10).unwrap());
+        assert_eq!(part_1(&inp), 13140);
     }
 
     #[test]
     fn test_part_2() {
-        let inp = parse_input(INPUT);
-        assert_eq!(part_2(inp), 
+        let inp = parse_input(&load_example(10).unwrap());
+        assert_eq!(part_2(&inp), 
 "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -1,37 +1,45 @@
-mod input;
+//! Day 11: Monkey in the Middle
 
 use std::{collections::VecDeque, fmt::Display};
 
+use crate::Solution;
+
+/// Right hand side of a monkey's operation.
 #[derive(Debug, Clone, Copy)]
-enum Operand {
+pub enum Operand {
     Old,
     Number(usize),
 }
 
+/// How a monkey changes the worry level of an item it inspects.
 #[derive(Debug, Clone, Copy)]
-enum Operation {
+pub enum Operation {
     Add(Operand),
     Multiply(Operand),
 }
 
+/// How a monkey decides where to throw an item.
 #[derive(Debug)]
-enum Test {
+pub enum Test {
     DivisibleBy(usize),
 }
 
+/// A monkey with the items it holds and its throwing rules.
 #[derive(Debug)]
-struct Monkey {
-    items: VecDeque<usize>,
-    operation: Operation,
-    test: Test,
-    true_monkey: usize,
-    false_monkey: usize,
-    inspections: usize,
-    divide_by_three: bool,
+pub struct Monkey {
+    pub items: VecDeque<usize>,
+    pub operation: Operation,
+    pub test: Test,
+    pub true_monkey: usize,
+    pub false_monkey: usize,
+    /// Number of items the monkey has inspected so far.
+    pub inspections: usize,
+    pub divide_by_three: bool,
 }
 
 impl Monkey {
-    fn process_item(&mut self) -> (usize, usize) {
+    /// Inspects the first item and returns the monkey it is thrown to and its new worry level.
+    pub fn process_item(&mut self) -> (usize, usize) {
         let mut worry_level = self.items.pop_front().unwrap();
 
         self.inspections += 1;
@@ -51,7 +59,7 @@ impl Monkey {
         }
 
         let test_success = match self.test {
-            Test::DivisibleBy(n) => worry_level % n == 0,
+            Test::DivisibleBy(n) => worry_level.is_multiple_of(n),
         };
 
         if test_success {
@@ -62,14 +70,16 @@ impl Monkey {
     }
 }
 
-struct Monkeys {
-    monkeys: Vec<Monkey>,
-    round: usize,
-    regularization: Option<usize>,
+/// All monkeys playing keep away.
+pub struct Monkeys {
+    pub monkeys: Vec<Monkey>,
+    pub round: usize,
+    /// Worry levels are taken modulo this number to keep them from overflowing.
+    pub regularization: Option<usize>,
 }
 
 impl Monkeys {
-    fn new() -> Monkeys {
+    pub fn new() -> Monkeys {
         Monkeys {
             monkeys: Vec::new(),
             round: 0,
@@ -77,12 +87,13 @@ impl Monkeys {
         }
     }
 
-    fn process_round(&mut self) {
+    /// Lets every monkey inspect and throw all of its items once.
+    pub fn process_round(&mut self) {
         for i in 0..self.monkeys.len() {
             while !self.monkeys[i].items.is_empty() {
                 let (next_monkey, mut worry_level) = self.monkeys[i].process_item();
-                if self.regularization.is_some() {
-                    worry_level %= self.regularization.unwrap();
+                if let Some(regularization) = self.regularization {
+                    worry_level %= regularization;
                 }
                 self.monkeys[next_monkey].items.push_back(worry_level);
             }
@@ -91,6 +102,12 @@ impl Monkeys {
     }
 }
 
+impl Default for Monkeys {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Display for Monkeys {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
@@ -115,7 +132,8 @@ impl Display for Monkeys {
     }
 }
 
-fn parse_input(input: &str, divide_by_three: bool) -> Monkeys {
+/// Parses the notes on all monkeys.
+pub fn parse_input(input: &str, divide_by_three: bool) -> Monkeys {
     let mut monkeys = Monkeys::new();
     let lines = input.lines().collect::<Vec<&str>>();
 
@@ -189,7 +207,8 @@ fn process_and_get_solution(mut monkeys: Monkeys, rounds: usize) -> usize {
     monkeys.monkeys[0].inspections * monkeys.monkeys[1].inspections
 }
 
-fn get_common_multiple(monkeys: &Monkeys) -> usize {
+/// Common multiple of all divisors, worry levels can be reduced modulo it without changing any test.
+pub fn get_common_multiple(monkeys: &Monkeys) -> usize {
     monkeys
             .monkeys
             .iter()
@@ -199,32 +218,47 @@ fn get_common_multiple(monkeys: &Monkeys) -> usize {
             .fold(1, |acc, x| if acc % x != 0 { acc * x } else { acc })
 }
 
-fn main() {
-    let inp = input::get_input(11);
-    let monkeys = parse_input(&inp, true);
-    println!("{}", process_and_get_solution(monkeys, 20));
-    let mut monkeys = parse_input(&inp, false);
-    let common_multiple = get_common_multiple(&monkeys);
-    monkeys.regularization = Some(common_multiple);
-    println!("{}", process_and_get_solution(monkeys, 10000));
+/// Monkey business after 20 rounds, and after 10000 rounds without relief.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        let monkeys = parse_input(input, true);
+        process_and_get_solution(monkeys, 20)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        let mut monkeys = parse_input(input, false);
+        let common_multiple = get_common_multiple(&monkeys);
+        monkeys.regularization = Some(common_multiple);
+        process_and_get_solution(monkeys, 10000)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = include_str!("../test_input/day11.txt");
+    use crate::examples::load_example;
 
     #[test]
     fn test_part_1() {
         assert_eq!(
-            process_and_get_solution(parse_input(INPUT, true), 20),
+            process_and_get_solution(parse_input(&load_example(11).unwrap(), true), 20),
             10605
         );
     }
 
     #[test]
     fn test_part_2() {
-        let mut monkeys = parse_input(INPUT, false);
+        let mut monkeys = parse_input(&load_example(11).unwrap(), false);
         let common_multiple = get_common_multiple(&monkeys);
         monkeys.regularization = Some(common_multiple);
         assert_eq!(process_and_get_solution(monkeys, 10000), 2713310158);
@@ -1,17 +1,23 @@
+//! Day 12: Hill Climbing Algorithm
+
 use std::collections::{BinaryHeap, HashSet};
 use std::cmp::{Ord, PartialOrd, Ordering};
-mod input;
 
-type Heightmap = Vec<Vec<char>>;
+use crate::Solution;
+
+/// Elevations `a` to `z`, indexed by `[y][x]`.
+pub type Heightmap = Vec<Vec<char>>;
 
+/// Position on the heightmap.
 #[derive(Debug,Hash,PartialEq,Eq,Clone,Copy)]
-struct Position {
-    x: usize,
-    y: usize,
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
 }
 
 impl Position {
-    fn neighbors(&self, rows: usize, columns: usize) -> Vec<Self> {
+    /// The up to four positions next to this one that are inside the map.
+    pub fn neighbors(&self, rows: usize, columns: usize) -> Vec<Self> {
         let mut neighbors = Vec::new();
         if self.x > 0 {
             neighbors.push(Position { x: self.x - 1, y: self.y });
@@ -44,11 +50,12 @@ impl Ord for QueueItem {
 
 impl PartialOrd for QueueItem {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.cost.cmp(&self.cost))
+        Some(self.cmp(other))
     }
 }
 
-fn parse_input(input: &str) -> (Heightmap, Position, Position) {
+/// Parses the heightmap and returns it with the start and end positions.
+pub fn parse_input(input: &str) -> (Heightmap, Position, Position) {
     let mut heightmap: Heightmap = input
         .lines()
         .map(|line| line.chars().collect())
@@ -123,11 +130,25 @@ fn part_2(heightmap: &Heightmap, start_position: Position) -> usize {
     panic!("No path found");
 }
 
-fn main() {
-    let inp = input::get_input(12);
-    let (heightmap, start_position, end_position) = parse_input(&inp);
-    println!("Part 1: {}", part_1(&heightmap, start_position, end_position).unwrap());
-    println!("Part 2: {}", part_2(&heightmap, end_position));
+/// Fewest steps from the start to the end, and from any square of elevation `a` to the end.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = (Heightmap, Position, Position);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1((heightmap, start_position, end_position): &Self::Input<'_>) -> usize {
+        part_1(heightmap, *start_position, *end_position).expect("No path found")
+    }
+
+    fn part2((heightmap, _, end_position): &Self::Input<'_>) -> usize {
+        part_2(heightmap, *end_position)
+    }
 }
 
 #[cfg(test)]
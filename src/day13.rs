@@ -1,12 +1,15 @@
-mod input;
+//! Day 13: Distress Signal
 
 use std::{
     cmp::{Ord, Ordering},
     str::Chars,
 };
 
+use crate::Solution;
+
+/// A packet value, either an integer or a list of values. Entries are ordered by the distress signal rules.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Entry {
+pub enum Entry {
     List(Vec<Entry>),
     Number(usize),
 }
@@ -63,7 +66,8 @@ fn get_packet_vector(packet_chars: &mut Chars) -> Entry {
     Entry::List(packet_vector)
 }
 
-fn parse_packet_pairs(input: &str) -> Vec<(Entry, Entry)> {
+/// Parses the pairs of packets separated by blank lines.
+pub fn parse_packet_pairs(input: &str) -> Vec<(Entry, Entry)> {
     let packet_pairs = input.split("\n\n");
     let mut packet_vec = Vec::new();
 
@@ -80,7 +84,8 @@ fn parse_packet_pairs(input: &str) -> Vec<(Entry, Entry)> {
     packet_vec
 }
 
-fn parse_all_packets(input: &str) -> Vec<Entry> {
+/// Parses all packets, ignoring the blank lines between pairs.
+pub fn parse_all_packets(input: &str) -> Vec<Entry> {
     let lines = input.lines();
     lines
         .filter(|line| !line.is_empty())
@@ -125,28 +130,45 @@ fn part2(packets: &mut Vec<Entry>) -> usize {
     i1 * i2
 }
 
-fn main() {
-    let inp = input::get_input(13);
-    let packets = parse_packet_pairs(&inp);
-    println!("Part 1: {}", part1(&packets));
-    let mut packets = parse_all_packets(&inp);
-    println!("Part 2: {}", part2(&mut packets));
+/// Sum of the indices of pairs in the right order, and the decoder key.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        let packets = parse_packet_pairs(input);
+        part1(&packets)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        let mut packets = parse_all_packets(input);
+        part2(&mut packets)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = include_str!("../test_input/day13.txt");
+    use crate::examples::load_example;
 
     #[test]
     fn test_part1() {
-        let packets = parse_packet_pairs(INPUT);
+        let input = load_example(13).unwrap();
+        let packets = parse_packet_pairs(&input);
         assert_eq!(part1(&packets), 13);
     }
 
     #[test]
     fn test_part2() {
-        let mut packets = parse_all_packets(INPUT);
+        let input = load_example(13).unwrap();
+        let mut packets = parse_all_packets(&input);
         assert_eq!(part2(&mut packets), 140);
     }
 }
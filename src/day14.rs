@@ -1,25 +1,31 @@
-mod input;
+//! Day 14: Regolith Reservoir
 
 use core::fmt;
 use std::{cmp, collections::HashSet};
 
-#[derive(Hash, PartialEq, Eq, Debug)]
-struct Position {
-    x: usize,
-    y: usize,
+use crate::Solution;
+
+/// Position in the cave, `y` grows downwards.
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
 }
 
-#[derive(Debug)]
-struct Grid {
-    rocks: HashSet<Position>,
-    sand: HashSet<Position>,
-    max: Position,
-    min: Position,
-    floor: Option<usize>,
+/// The rocks and sand in the cave.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    pub rocks: HashSet<Position>,
+    pub sand: HashSet<Position>,
+    /// Bounding box of the rocks.
+    pub max: Position,
+    pub min: Position,
+    /// Height of the infinite floor, if there is one.
+    pub floor: Option<usize>,
 }
 
 impl Grid {
-    fn new() -> Grid {
+    pub fn new() -> Grid {
         Grid {
             rocks: HashSet::new(),
             sand: HashSet::new(),
@@ -34,7 +40,7 @@ impl Grid {
             floor: None,
         }
     }
-    fn insert_rock(&mut self, pos: Position) {
+    pub fn insert_rock(&mut self, pos: Position) {
         if pos.x > self.max.x {
             self.max.x = pos.x;
         }
@@ -50,16 +56,23 @@ impl Grid {
         self.rocks.insert(pos);
     }
 
-    fn contains(&self, pos: &Position) -> bool {
+    /// Whether the position is blocked by rock or sand.
+    pub fn contains(&self, pos: &Position) -> bool {
         self.rocks.contains(pos) || self.sand.contains(pos)
     }
 }
 
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "\x1B[2J\x1B[1;1H")?;
 
-        for y in 0..(if self.floor.is_some() {self.floor.unwrap()} else {self.max.y}) + 1 {
+        for y in 0..self.floor.unwrap_or(self.max.y) + 1 {
             for x in self.min.x..self.max.x + 1 {
                 let pos = Position { x, y };
                 if self.rocks.contains(&pos) {
@@ -78,7 +91,8 @@ impl fmt::Display for Grid {
     }
 }
 
-fn parse_input(inp: &str) -> Grid {
+/// Parses the rock paths into a grid.
+pub fn parse_input(inp: &str) -> Grid {
     let mut grid = Grid::new();
 
     for line in inp.lines() {
@@ -100,7 +114,8 @@ fn parse_input(inp: &str) -> Grid {
     grid
 }
 
-fn simulate_sand(grid: &mut Grid) -> usize {
+/// Drops sand from `500,0` until it falls into the abyss or blocks the source, returns the units that came to rest.
+pub fn simulate_sand(grid: &mut Grid) -> usize {
     for i in 0..usize::MAX {
         let mut x = 500;
         let mut y = 0;
@@ -131,17 +146,28 @@ fn simulate_sand(grid: &mut Grid) -> usize {
     10
 }
 
-fn main() {
-    let inp = input::get_input(14);
-    let mut grid = parse_input(&inp);
-    let solution_1 = simulate_sand(&mut grid);
-    println!("{}", grid);
-    grid.floor = Some(grid.max.y + 2);
-    grid.sand = HashSet::new();
-    let solution_2 = simulate_sand(&mut grid);
-    println!("{}", grid);
-    println!("Part 1: {}", solution_1);
-    println!("Part 2: {}", solution_2);
+/// Units of sand at rest before sand flows into the abyss, and before the source is blocked.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(grid: &Self::Input<'_>) -> usize {
+        let mut grid = grid.clone();
+        simulate_sand(&mut grid)
+    }
+
+    fn part2(grid: &Self::Input<'_>) -> usize {
+        let mut grid = grid.clone();
+        grid.floor = Some(grid.max.y + 2);
+        simulate_sand(&mut grid)
+    }
 }
 
 #[cfg(test)]
@@ -1,41 +1,57 @@
-use std::{
-    ops::Range,
-    str::FromStr
+//! Day 15: Beacon Exclusion Zone
+
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{
+    interval::{IntervalSet, Section},
+    Solution,
 };
 
-mod input;
+/// Positions in row 2000000 without a beacon, and the tuning frequency of the distress beacon.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<Sensor>;
+    type Part1 = usize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(sensors: &Self::Input<'_>) -> usize {
+        part_1(sensors, 2000000)
+    }
 
-fn main() {
-    let inp = input::get_input(15);
-    let sensors = parse_input(&inp);
-    println!("{}", part_1(&sensors, 2000000));
-    println!("{}", part_2(&sensors, 4000000));
+    fn part2(sensors: &Self::Input<'_>) -> isize {
+        part_2(sensors, 4000000)
+    }
 }
 
-type Position = (isize, isize);
+/// `(x, y)` position.
+pub type Position = (isize, isize);
 
+/// A sensor and the closest beacon it detected, parsed from a line of the report.
 #[derive(Debug)]
-struct Sensor {
-    position: Position,
-    closest_beacon: Position,
+pub struct Sensor {
+    pub position: Position,
+    pub closest_beacon: Position,
 }
 
 impl Sensor {
-    fn distance(&self) -> isize {
+    /// Manhattan distance between the sensor and its closest beacon.
+    pub fn distance(&self) -> isize {
         let (x1, y1) = self.position;
         let (x2, y2) = self.closest_beacon;
         (x1 - x2).abs() + (y1 - y2).abs()
     }
 
-    fn get_empty_positions_x(&self, row: isize) -> Option<Range<isize>> {
-        let distance = self.distance();
-
-        let x_range = distance - (self.position.1 - row).abs();
-        if x_range <= 0 {
-            return None;
-        }
-
-        Some(self.position.0 - x_range..self.position.0 + x_range + 1)
+    /// Section of x positions in `row` within the distance of the sensor, only its closest
+    /// beacon can be there.
+    pub fn get_empty_positions_x(&self, row: isize) -> Option<Section> {
+        let x_range = self.distance() - (self.position.1 - row).abs();
+        let x = self.position.0 as i64;
+        Section::new(x - x_range as i64, x + x_range as i64)
     }
 }
 
@@ -56,7 +72,8 @@ impl FromStr for Sensor {
     }
 }
 
-fn parse_input(inp: &str) -> Vec<Sensor> {
+/// Parses all sensors of the report.
+pub fn parse_input(inp: &str) -> Vec<Sensor> {
     let mut sensors = Vec::new();
     for line in inp.lines() {
         sensors.push(Sensor::from_str(line).unwrap());
@@ -65,49 +82,29 @@ fn parse_input(inp: &str) -> Vec<Sensor> {
 }
 
 fn part_1(sensors: &[Sensor], row: isize) -> usize {
-    let mut min_x = isize::MAX;
-    let mut max_x = isize::MIN;
-
-    for sensor in sensors {
-        let range = sensor.get_empty_positions_x(row);
-        if let Some(range) = range {
-            if range.start < min_x {
-                min_x = range.start;
-            }
-            if range.end > max_x {
-                max_x = range.end;
-            }
-        }
-    }
-
-    (max_x - min_x - 1) as usize
+    let covered: IntervalSet = sensors
+        .iter()
+        .filter_map(|sensor| sensor.get_empty_positions_x(row))
+        .collect();
+    let beacons: HashSet<isize> = sensors
+        .iter()
+        .filter(|sensor| sensor.closest_beacon.1 == row)
+        .map(|sensor| sensor.closest_beacon.0)
+        .collect();
+
+    covered.len() as usize - beacons.len()
 }
 
 fn part_2(sensors: &[Sensor], max: isize) -> isize {
-    for y in 0..max {
-        let mut ranges = Vec::new();
-
-        for sensor in sensors {
-            let range = sensor.get_empty_positions_x(y);
-            if let Some(range) = range {
-                ranges.push(range);
-            }
-        }
-        ranges.sort_by_key(|range| range.start);
-
-        if ranges.first().unwrap().start > 0 {
-            return y;
-        }
-
-        let mut last_end = ranges[0].end;
-
-        for range in ranges {
-            if range.start > last_end {
-                return last_end * 4000000 + y;
-            }
-            if range.end > last_end {
-                last_end = range.end;
-            }
+    let within = Section::new(0, max as i64).unwrap();
+    for y in 0..=max {
+        let covered: IntervalSet = sensors
+            .iter()
+            .filter_map(|sensor| sensor.get_empty_positions_x(y))
+            .collect();
+
+        if let Some(gap) = covered.gaps(&within).first() {
+            return gap.start as isize * 4000000 + y;
         }
     }
 
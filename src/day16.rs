@@ -1,11 +1,15 @@
-mod input;
+//! Day 16: Proboscidea Volcanium
 
 use std::{collections::{BinaryHeap, HashMap, HashSet}, hash::Hash};
 
+use crate::Solution;
+
+/// A valve with its flow rate and tunnels.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Valve {
-    flow_rate: usize,
-    connects_to: Vec<(String, usize)>,
+pub struct Valve {
+    pub flow_rate: usize,
+    /// Names of the valves reachable from this one and the minutes it takes to get there.
+    pub connects_to: Vec<(String, usize)>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -22,16 +26,29 @@ impl Ord for QueueItem<'_> {
 
 impl PartialOrd for QueueItem<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.cost.cmp(&self.cost))
+        Some(self.cmp(other))
     }
 }
 
-fn main() {
-    let inp = input::get_input(16);
-    let valves = parse_input(&inp);
-    //println!("Valves: {:?}", valves);
-    println!("Part 1: {}", part_1(valves.clone()));
-    println!("Part 2: {}", part_2(valves.clone()));
+/// Most pressure released in 30 minutes alone, and in 26 minutes together with an elephant.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = HashMap<&'a str, Valve>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(valves: &Self::Input<'_>) -> usize {
+        part_1(valves.clone())
+    }
+
+    fn part2(valves: &Self::Input<'_>) -> usize {
+        part_2(valves.clone())
+    }
 }
 
 fn compress_graph(valves: HashMap<&str, Valve>) -> HashMap<&str, Valve> {
@@ -52,7 +69,8 @@ fn compress_graph(valves: HashMap<&str, Valve>) -> HashMap<&str, Valve> {
     new_valves
 }
 
-fn parse_input(inp: &str) -> HashMap<&str, Valve> {
+/// Parses the scan and compresses it to `AA` and the valves with a positive flow rate.
+pub fn parse_input(inp: &str) -> HashMap<&str, Valve> {
     let mut valves = HashMap::new();
 
     for line in inp.lines() {
@@ -1,10 +1,14 @@
+//! Day 17: Pyroclastic Flow
+
 use std::collections::{HashMap, HashSet, VecDeque};
 
-mod input;
+use crate::Solution;
 
-type Position = (i64, i64);
+/// `(height, column)` position in the chamber.
+pub type Position = (i64, i64);
 
-type RockShape = Vec<Position>;
+/// Positions occupied by a rock, relative to its bottom left corner.
+pub type RockShape = Vec<Position>;
 
 struct Chamber {
     occupied_positions: HashSet<Position>,
@@ -34,7 +38,6 @@ impl Chamber {
         position: Position,
         direction: char,
     ) -> Position {
-        let position = position;
         match direction {
             '>' => {
                 if self.does_collide(rock_shape, (position.0, position.1 + 1)) {
@@ -80,13 +83,29 @@ impl Chamber {
     }
 }
 
-fn main() {
-    let inp = input::get_input(17);
-    println!("Part 1: {}", part_1(&inp, 2022));
-    println!("Part 2: {}", part_1(&inp, 1000000000000));
+/// Height of the tower after 2022 rocks, and after 1000000000000 rocks.
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = &'a str;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> i64 {
+        part_1(input, 2022)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> i64 {
+        part_1(input, 1000000000000)
+    }
 }
 
-fn get_rock_shapes() -> Vec<RockShape> {
+/// The five rock shapes in the order they fall.
+pub fn get_rock_shapes() -> Vec<RockShape> {
     vec![
         vec![(0, 0), (0, 1), (0, 2), (0, 3)],
         vec![(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
@@ -152,7 +171,7 @@ fn part_1(inp: &str, n: usize) -> i64 {
         i = (i + 1) % rock_shapes.len();
         round += 1;
     }
-    total as i64 + chamber.highest_position + 1
+    total + chamber.highest_position + 1
 }
 
 #[cfg(test)]
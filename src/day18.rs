@@ -1,15 +1,20 @@
-mod input;
+//! Day 18: Boiling Boulders
+
 use std::{collections::{HashMap, HashSet}};
 
+use crate::Solution;
+
+/// Position of a cube in the scan.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Position {
-    x: i32,
-    y: i32,
-    z: i32,
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
 }
 
 impl Position {
-    fn get_distance(&self, other: &Position) -> i32 {
+    /// Manhattan distance to another position.
+    pub fn get_distance(&self, other: &Position) -> i32 {
         (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
     }
 }
@@ -112,11 +117,25 @@ fn part_1(inp: &str) -> (usize, usize) {
     (not_blocked_cube_sides, surface_area)
 }
 
-fn main() {
-    let inp = input::get_input(18);
-    let (part_1, part_2) = part_1(&inp);
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+/// Surface area of the droplet, and the part of it that is reachable from outside.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        part_1(input).0
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        part_1(input).1
+    }
 }
 
 #[cfg(test)]
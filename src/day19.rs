@@ -1,7 +1,15 @@
-use std::{str::FromStr, collections::HashMap, io::{stdin, Read}};
+//! Day 19: Not Enough Minerals
+//!
+//! Work in progress: no part is solved yet, so the day has no `Solution` and is not registered.
 
+// The solver is only reachable from its tests until it is solved
+#![allow(dead_code)]
+
+use std::{str::FromStr, collections::HashMap};
+
+/// A resource collected by robots.
 #[derive(Debug,Clone,Copy,Hash,PartialEq,Eq)]
-enum Resource {
+pub enum Resource {
     Ore,
     Clay,
     Obsidian,
@@ -22,10 +30,11 @@ impl FromStr for Resource {
     }
 }
 
+/// A robot blueprint: what it collects and what it costs to build.
 #[derive(Debug)]
-struct Robot {
-    produces: Resource,
-    required_resources: Vec<(Resource, u32)>,
+pub struct Robot {
+    pub produces: Resource,
+    pub required_resources: Vec<(Resource, u32)>,
 }
 
 impl FromStr for Robot {
@@ -49,7 +58,7 @@ impl FromStr for Robot {
     }
 }
 
-fn traverse(robot_config: &Vec<Robot>, mut robots: Vec<Resource>, qeued_robot: Option<Resource>, mut resources: HashMap<Resource, u32>, mut remaining_minutes: u32) -> u32 {
+fn traverse(robot_config: &Vec<Robot>, mut robots: Vec<Resource>, qeued_robot: Option<Resource>, mut resources: HashMap<Resource, u32>, remaining_minutes: u32) -> u32 {
 
 
     for robot in &robots {
@@ -68,7 +77,6 @@ fn traverse(robot_config: &Vec<Robot>, mut robots: Vec<Resource>, qeued_robot: O
     //println!("Minute {}: {:?} {:?}", 24 - remaining_minutes, resources, robots);
 
     let mut max = 0;
-    let mut max_action: Option<Resource> = None;
 
     for possible_robot in robot_config {
         if robots.iter().filter(|r| **r == possible_robot.produces).count() > 5 {
@@ -96,8 +104,8 @@ fn traverse(robot_config: &Vec<Robot>, mut robots: Vec<Resource>, qeued_robot: O
             }
         }
         //println!("Wait time: {}, remaining: {}", wait_time, remaining_minutes);
-        if remaining_minutes >= wait_time + 1 {
-            let mut new_robots = robots.clone();
+        if remaining_minutes > wait_time {
+            let new_robots = robots.clone();
 
             let mut new_resources = resources.clone();
             for robot in &robots {
@@ -110,7 +118,6 @@ fn traverse(robot_config: &Vec<Robot>, mut robots: Vec<Resource>, qeued_robot: O
             let result = traverse(robot_config, new_robots, Some(possible_robot.produces), new_resources, remaining_minutes - wait_time-1);
             if result > max {
                 max = result;
-                max_action = Some(possible_robot.produces);
             }
 
         }
@@ -121,7 +128,6 @@ fn traverse(robot_config: &Vec<Robot>, mut robots: Vec<Resource>, qeued_robot: O
         }
         *resources.get(&Resource::Geode).unwrap_or(&0)
     } else {
-        //println!("The best option at minute {} is to produce a {:?} robot", 24 - remaining_minutes, max_action.unwrap());
         max
     }
 }
@@ -140,10 +146,6 @@ fn part_1 (input: &str) -> u32 {
     0
 }
 
-fn main() {
-    unimplemented!();
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,88 +1,664 @@
-mod input;
+//! Day 2: Rock Paper Scissors
 
-#[derive(Debug, Clone, Copy)]
-enum Hand {
+use crate::Solution;
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+/// A shape played in a round of rock paper scissors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
     Rock,
     Paper,
     Scissors,
 }
 
+/// Result of a round for the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
 impl Hand {
-    fn get_score(&self, other: &Hand) -> u32 {
+    const ALL: [Hand; 3] = [Hand::Rock, Hand::Paper, Hand::Scissors];
+
+    /// Index of the hand in `Rules::rock_paper_scissors`.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Hand {
+        Hand::ALL[index]
+    }
+
+    pub fn shape_score(&self) -> u32 {
+        Rules::rock_paper_scissors().shape_score(self.index())
+    }
+
+    /// Outcome of playing `self` against `other`.
+    pub fn outcome(&self, other: &Hand) -> Outcome {
+        Rules::rock_paper_scissors().outcome(self.index(), other.index())
+    }
+
+    /// Score of playing `self` against `other`: the shape score plus 0, 3 or 6 for the outcome.
+    pub fn get_score(&self, other: &Hand) -> u32 {
+        self.shape_score() + self.outcome(other).score()
+    }
+
+    /// The hand that beats `hand`.
+    pub fn get_winning_hand(hand: &Hand) -> Self {
+        Hand::for_outcome(hand, Outcome::Win)
+    }
+
+    /// The hand that loses against `hand`.
+    pub fn get_losing_hand(hand: &Hand) -> Self {
+        Hand::for_outcome(hand, Outcome::Lose)
+    }
+
+    /// The hand to play against `opponent` to get `outcome`.
+    pub fn for_outcome(opponent: &Hand, outcome: Outcome) -> Self {
+        let rules = Rules::rock_paper_scissors();
+        // Every outcome is reachable in a cycle of three
+        Hand::from_index(rules.hand_for_outcome(opponent.index(), outcome).unwrap())
+    }
+}
+
+/// Errors for an invalid set of rules.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RulesError {
+    /// Every hand needs exactly one shape score.
+    ScoreCount { hands: usize, scores: usize },
+    UnknownHand(String),
+    /// A hand is listed as beating itself.
+    BeatsItself(String),
+    /// Two hands are listed as beating each other.
+    Conflict(String, String),
+    /// Neither hand beats the other.
+    Undecided(String, String),
+    /// A cycle where every hand beats as many hands as it loses to needs an odd number of hands.
+    EvenCycle(usize),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Hand::Rock => match other {
-                Hand::Rock => 4,
-                Hand::Paper => 1,
-                Hand::Scissors => 7,
-            },
-            Hand::Paper => match other {
-                Hand::Rock => 8,
-                Hand::Paper => 5,
-                Hand::Scissors => 2,
-            },
-            Hand::Scissors => match other {
-                Hand::Rock => 3,
-                Hand::Paper => 9,
-                Hand::Scissors => 6,
-            },
+            RulesError::ScoreCount { hands, scores } => {
+                write!(f, "{} hands but {} shape scores", hands, scores)
+            }
+            RulesError::UnknownHand(hand) => write!(f, "Unknown hand {}", hand),
+            RulesError::BeatsItself(hand) => write!(f, "{} beats itself", hand),
+            RulesError::Conflict(a, b) => write!(f, "{} and {} beat each other", a, b),
+            RulesError::Undecided(a, b) => write!(f, "Neither {} nor {} wins", a, b),
+            RulesError::EvenCycle(n) => write!(f, "A cycle of {} hands cannot be balanced", n),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+/// A variant of rock paper scissors as data: the hands, their shape scores and which hand
+/// beats which. Hands are referred to by their index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    names: Vec<String>,
+    shape_scores: Vec<u32>,
+    /// `beats[a][b]` is true if hand `a` beats hand `b`.
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    /// Builds rules from the `(winner, loser)` pairs. Every two different hands need exactly
+    /// one winner.
+    pub fn new(
+        names: &[&str],
+        shape_scores: &[u32],
+        beats: &[(&str, &str)],
+    ) -> Result<Rules, RulesError> {
+        if names.len() != shape_scores.len() {
+            return Err(RulesError::ScoreCount {
+                hands: names.len(),
+                scores: shape_scores.len(),
+            });
+        }
+
+        let index = |name: &str| {
+            names
+                .iter()
+                .position(|n| *n == name)
+                .ok_or_else(|| RulesError::UnknownHand(name.to_string()))
+        };
+        let mut table = vec![vec![false; names.len()]; names.len()];
+        for (winner, loser) in beats {
+            let (w, l) = (index(winner)?, index(loser)?);
+            if w == l {
+                return Err(RulesError::BeatsItself(winner.to_string()));
+            }
+            table[w][l] = true;
+        }
+
+        for a in 0..names.len() {
+            for b in a + 1..names.len() {
+                let pair = (names[a].to_string(), names[b].to_string());
+                match (table[a][b], table[b][a]) {
+                    (true, true) => return Err(RulesError::Conflict(pair.0, pair.1)),
+                    (false, false) => return Err(RulesError::Undecided(pair.0, pair.1)),
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(Rules {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            shape_scores: shape_scores.to_vec(),
+            beats: table,
+        })
+    }
+
+    /// A balanced tournament of an odd number of hands: every hand beats the `(n - 1) / 2`
+    /// hands before it in the cycle. Shape scores are 1, 2, 3 ... in order.
+    pub fn cycle(names: &[&str]) -> Result<Rules, RulesError> {
+        let n = names.len();
+        if n.is_multiple_of(2) {
+            return Err(RulesError::EvenCycle(n));
         }
+
+        let beats: Vec<(&str, &str)> = (0..n)
+            .flat_map(|winner| {
+                (1..=n / 2).map(move |offset| (names[winner], names[(winner + n - offset) % n]))
+            })
+            .collect();
+        let scores: Vec<u32> = (1..=n as u32).collect();
+        Rules::new(names, &scores, &beats)
+    }
+
+    /// The rules of the puzzle, hands in the order of `Hand`.
+    pub fn rock_paper_scissors() -> &'static Rules {
+        static RULES: OnceLock<Rules> = OnceLock::new();
+        RULES.get_or_init(|| Rules::cycle(&["Rock", "Paper", "Scissors"]).unwrap())
+    }
+
+    /// Rock paper scissors lizard Spock, scored 1 to 5 in that order.
+    pub fn rock_paper_scissors_lizard_spock() -> Rules {
+        Rules::new(
+            &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+            &[1, 2, 3, 4, 5],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        )
+        .unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
     }
 
-    fn get_winning_hand(hand: &Hand) -> Self{
-        match hand {
-            Hand::Rock => Hand::Paper,
-            Hand::Paper => Hand::Scissors,
-            Hand::Scissors => Hand::Rock,
+    pub fn name(&self, hand: usize) -> &str {
+        &self.names[hand]
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn shape_score(&self, hand: usize) -> u32 {
+        self.shape_scores[hand]
+    }
+
+    /// Outcome of playing `hand` against `other`.
+    pub fn outcome(&self, hand: usize, other: usize) -> Outcome {
+        if hand == other {
+            Outcome::Draw
+        } else if self.beats[hand][other] {
+            Outcome::Win
+        } else {
+            Outcome::Lose
         }
     }
 
-    fn get_losing_hand(hand: &Hand) -> Self{
-        match hand {
-            Hand::Rock => Hand::Scissors,
-            Hand::Paper => Hand::Rock,
-            Hand::Scissors => Hand::Paper,
+    /// Score of playing `hand` against `other`.
+    pub fn score(&self, hand: usize, other: usize) -> u32 {
+        self.shape_score(hand) + self.outcome(hand, other).score()
+    }
+
+    /// The hand to play against `opponent` to get `outcome`. If several hands do, the one
+    /// with the highest shape score is taken.
+    pub fn hand_for_outcome(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.len())
+            .filter(|&hand| self.outcome(hand, opponent) == outcome)
+            .max_by_key(|&hand| self.shape_score(hand))
+    }
+
+    /// Total score of the rounds, given as the hand of the opponent and the own hand.
+    pub fn play(&self, rounds: &[(usize, usize)]) -> u32 {
+        rounds
+            .iter()
+            .map(|&(opponent, hand)| self.score(hand, opponent))
+            .sum()
+    }
+}
+
+/// Errors for a line of the strategy guide.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseRoundError {
+    /// The opponent column is not `A`, `B` or `C`.
+    InvalidHand(String),
+    /// The second column is not `X`, `Y` or `Z`.
+    InvalidCode(String),
+    /// The line does not have exactly two columns.
+    InvalidRound(String),
+}
+
+impl fmt::Display for ParseRoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRoundError::InvalidHand(s) => {
+                write!(f, "Invalid hand {:?}, expected A, B or C", s)
+            }
+            ParseRoundError::InvalidCode(s) => {
+                write!(f, "Invalid code {:?}, expected X, Y or Z", s)
+            }
+            ParseRoundError::InvalidRound(s) => {
+                write!(f, "Invalid round {:?}, expected two columns", s)
+            }
         }
     }
 }
 
-impl From<&str> for Hand {
-    fn from(s: &str) -> Self {
+impl std::error::Error for ParseRoundError {}
+
+impl FromStr for Hand {
+    type Err = ParseRoundError;
+
+    /// Parses the opponent column.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "X" | "A" => Hand::Rock,
-            "Y" | "B" => Hand::Paper,
-            "Z" | "C" => Hand::Scissors,
-            _ => panic!("Invalid hand"),
+            "A" => Ok(Hand::Rock),
+            "B" => Ok(Hand::Paper),
+            "C" => Ok(Hand::Scissors),
+            _ => Err(ParseRoundError::InvalidHand(s.to_string())),
         }
     }
 }
 
-fn main() {
-    let input_string = input::get_input(2);
+/// The second column of the guide, its meaning is what the puzzle is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Code {
+    X,
+    Y,
+    Z,
+}
 
-    let score: u32 = input_string.lines().fold(0, |score, line| {
-        let hands = line.split_whitespace().map(Hand::from).collect::<Vec<Hand>>();
-        assert!(hands.len() == 2, "Invalid match string");
-        
-        score + hands[1].get_score(&hands[0])
-    });
+impl Code {
+    /// Part one reads the code as the hand to play.
+    pub fn as_hand(&self) -> Hand {
+        match self {
+            Code::X => Hand::Rock,
+            Code::Y => Hand::Paper,
+            Code::Z => Hand::Scissors,
+        }
+    }
 
-    println!("Score: {}", score);
+    /// Part two reads the code as the outcome to reach.
+    pub fn as_outcome(&self) -> Outcome {
+        match self {
+            Code::X => Outcome::Lose,
+            Code::Y => Outcome::Draw,
+            Code::Z => Outcome::Win,
+        }
+    }
+}
 
-    // Part 2
-    let score2 = input_string.lines().fold(0, |score, line| {
-        let chars = line.split_whitespace().collect::<Vec<&str>>();
-        assert!(chars.len() == 2, "Invalid match string");
+impl FromStr for Code {
+    type Err = ParseRoundError;
 
-        let hand1 = Hand::from(chars[0]);
-        let hand2 = match chars[1] {
-            "X" => Hand::get_losing_hand(&hand1),
-            "Y" => hand1,
-            "Z" => Hand::get_winning_hand(&hand1),
-            _ => panic!("Invalid hand"),
-        };
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Code::X),
+            "Y" => Ok(Code::Y),
+            "Z" => Ok(Code::Z),
+            _ => Err(ParseRoundError::InvalidCode(s.to_string())),
+        }
+    }
+}
 
-        score + hand2.get_score(&hand1)
-    });
+/// A line of the strategy guide, like `A Y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: Hand,
+    pub code: Code,
+}
+
+impl Round {
+    /// Score when the code is the hand to play.
+    pub fn score_as_hand(&self) -> u32 {
+        self.code.as_hand().get_score(&self.opponent)
+    }
+
+    /// Score when the code is the outcome to reach.
+    pub fn score_as_outcome(&self) -> u32 {
+        Hand::for_outcome(&self.opponent, self.code.as_outcome()).get_score(&self.opponent)
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseRoundError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<&str>>()[..] {
+            [opponent, code] => Ok(Round {
+                opponent: opponent.parse()?,
+                code: code.parse()?,
+            }),
+            _ => Err(ParseRoundError::InvalidRound(s.to_string())),
+        }
+    }
+}
+
+/// Parses the strategy guide, errors name the 1-based line. Empty lines are skipped.
+pub fn parse_guide(input: &str) -> Result<Vec<Round>, (usize, ParseRoundError)> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| line.parse().map_err(|e| (i + 1, e)))
+        .collect()
+}
+
+pub fn part_1(rounds: &[Round]) -> u32 {
+    rounds.iter().map(Round::score_as_hand).sum()
+}
+
+pub fn part_2(rounds: &[Round]) -> u32 {
+    rounds.iter().map(Round::score_as_outcome).sum()
+}
+
+/// A way to read the X/Y/Z column of the guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// The codes are the hands to play, X/Y/Z in order.
+    Hands([Hand; 3]),
+    /// The codes are the outcomes to reach, as in part two.
+    Outcomes,
+}
+
+impl Interpretation {
+    /// The 6 mappings of the codes to hands followed by the outcome reading.
+    pub fn all() -> Vec<Interpretation> {
+        let mut all = Vec::new();
+        for x in Hand::ALL {
+            for y in Hand::ALL.into_iter().filter(|&y| y != x) {
+                for z in Hand::ALL.into_iter().filter(|&z| z != x && z != y) {
+                    all.push(Interpretation::Hands([x, y, z]));
+                }
+            }
+        }
+        all.push(Interpretation::Outcomes);
+        all
+    }
+
+    /// The hand played in a round.
+    pub fn hand(&self, round: &Round) -> Hand {
+        match self {
+            Interpretation::Hands(hands) => hands[round.code as usize],
+            Interpretation::Outcomes => Hand::for_outcome(&round.opponent, round.code.as_outcome()),
+        }
+    }
+
+    pub fn score(&self, rounds: &[Round]) -> u32 {
+        rounds
+            .iter()
+            .map(|round| self.hand(round).get_score(&round.opponent))
+            .sum()
+    }
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Interpretation::Hands([x, y, z]) => write!(f, "X={:?} Y={:?} Z={:?}", x, y, z),
+            Interpretation::Outcomes => write!(f, "X=Lose Y=Draw Z=Win"),
+        }
+    }
+}
+
+/// Every interpretation of the guide with its score, highest score first. Ties keep the
+/// order of `Interpretation::all`.
+pub fn rank_interpretations(rounds: &[Round]) -> Vec<(Interpretation, u32)> {
+    let mut ranked: Vec<(Interpretation, u32)> = Interpretation::all()
+        .into_iter()
+        .map(|interpretation| (interpretation, interpretation.score(rounds)))
+        .collect();
+    ranked.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    ranked
+}
+
+/// Highest score reachable against the opponent, choosing the best hand every round.
+pub fn max_score(opponents: &[Hand]) -> u32 {
+    opponents
+        .iter()
+        .map(|opponent| Hand::ALL.iter().map(|hand| hand.get_score(opponent)).max().unwrap())
+        .sum()
+}
+
+/// Lowest score reachable against the opponent, choosing the worst hand every round.
+pub fn min_score(opponents: &[Hand]) -> u32 {
+    opponents
+        .iter()
+        .map(|opponent| Hand::ALL.iter().map(|hand| hand.get_score(opponent)).min().unwrap())
+        .sum()
+}
+
+/// Total score when following the strategy guide, reading X/Y/Z as hands and as outcomes.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<Round>;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    println!("Score2: {}", score2);
-}
\ No newline at end of file
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        parse_guide(input).map_err(|(line, e)| format!("Line {}: {}", line, e).into())
+    }
+
+    fn part1(rounds: &Self::Input<'_>) -> u32 {
+        part_1(rounds)
+    }
+
+    fn part2(rounds: &Self::Input<'_>) -> u32 {
+        part_2(rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "A Y
+B X
+C Z";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(part_1(&parse_guide(INPUT).unwrap()), 15);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(part_2(&parse_guide(INPUT).unwrap()), 12);
+    }
+
+    #[test]
+    fn test_get_score() {
+        use Hand::*;
+        let expected = [
+            (Rock, [4, 1, 7]),
+            (Paper, [8, 5, 2]),
+            (Scissors, [3, 9, 6]),
+        ];
+        for (hand, scores) in expected {
+            for (other, score) in [Rock, Paper, Scissors].iter().zip(scores) {
+                assert_eq!(hand.get_score(other), score, "{:?} against {:?}", hand, other);
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_outcome() {
+        for opponent in [Hand::Rock, Hand::Paper, Hand::Scissors] {
+            for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+                assert_eq!(Hand::for_outcome(&opponent, outcome).outcome(&opponent), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rules_match_puzzle() {
+        let rules = Rules::rock_paper_scissors();
+        for hand in Hand::ALL {
+            assert_eq!(rules.name(hand.index()), format!("{:?}", hand));
+        }
+
+        let rounds: Vec<(usize, usize)> = parse_guide(INPUT)
+            .unwrap()
+            .iter()
+            .map(|round| (round.opponent.index(), round.code.as_hand().index()))
+            .collect();
+        assert_eq!(rules.play(&rounds), 15);
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_lizard_spock() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let hand = |name| rules.index(name).unwrap();
+
+        assert_eq!(rules.outcome(hand("Spock"), hand("Rock")), Outcome::Win);
+        assert_eq!(rules.outcome(hand("Spock"), hand("Lizard")), Outcome::Lose);
+        assert_eq!(rules.score(hand("Lizard"), hand("Paper")), 4 + 6);
+        // Rock and Spock both beat Scissors, Spock scores more
+        assert_eq!(
+            rules.hand_for_outcome(hand("Scissors"), Outcome::Win),
+            Some(hand("Spock"))
+        );
+
+        // The same relation as a cycle in another order
+        let cycle = Rules::cycle(&["Rock", "Spock", "Paper", "Lizard", "Scissors"]).unwrap();
+        for a in 0..rules.len() {
+            for b in 0..rules.len() {
+                let index = |hand| cycle.index(rules.name(hand)).unwrap();
+                assert_eq!(rules.outcome(a, b), cycle.outcome(index(a), index(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        let names = ["A", "B", "C", "D", "E", "F", "G"];
+        let rules = Rules::cycle(&names).unwrap();
+        for hand in 0..rules.len() {
+            let wins = (0..rules.len())
+                .filter(|&other| rules.outcome(hand, other) == Outcome::Win)
+                .count();
+            assert_eq!(wins, 3);
+        }
+        assert_eq!(Rules::cycle(&["A", "B"]), Err(RulesError::EvenCycle(2)));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert_eq!(
+            Rules::new(&["A", "B", "C"], &[1, 2, 3], &[("A", "B"), ("B", "C")]),
+            Err(RulesError::Undecided("A".to_string(), "C".to_string()))
+        );
+        assert_eq!(
+            Rules::new(&["A", "B"], &[1, 2], &[("A", "B"), ("B", "A")]),
+            Err(RulesError::Conflict("A".to_string(), "B".to_string()))
+        );
+        assert_eq!(
+            Rules::new(&["A"], &[1], &[("A", "A")]),
+            Err(RulesError::BeatsItself("A".to_string()))
+        );
+        assert_eq!(
+            Rules::new(&["A"], &[1], &[("A", "X")]),
+            Err(RulesError::UnknownHand("X".to_string()))
+        );
+        assert!(matches!(
+            Rules::new(&["A"], &[], &[]),
+            Err(RulesError::ScoreCount { hands: 1, scores: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_max_min_score() {
+        let opponents: Vec<Hand> = parse_guide(INPUT).unwrap().iter().map(|r| r.opponent).collect();
+        // Paper against Rock, Scissors against Paper, Rock against Scissors
+        assert_eq!(max_score(&opponents), 8 + 9 + 7);
+        // Scissors against Rock, Rock against Paper, Paper against Scissors
+        assert_eq!(min_score(&opponents), 3 + 1 + 2);
+    }
+
+    #[test]
+    fn test_rank_interpretations() {
+        use Hand::*;
+        let ranked = rank_interpretations(&parse_guide(INPUT).unwrap());
+
+        let scores: Vec<u32> = ranked.iter().map(|(_, score)| *score).collect();
+        assert_eq!(scores, [24, 15, 15, 15, 15, 12, 6]);
+        // The best reading wins every round, as with the maximal score
+        assert_eq!(ranked[0].0, Interpretation::Hands([Scissors, Paper, Rock]));
+        assert_eq!(ranked[1].0, Interpretation::Hands([Rock, Paper, Scissors]));
+        assert_eq!(ranked[5].0, Interpretation::Outcomes);
+
+        assert_eq!(ranked[0].0.to_string(), "X=Scissors Y=Paper Z=Rock");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "A Y".parse(),
+            Ok(Round {
+                opponent: Hand::Rock,
+                code: Code::Y
+            })
+        );
+        assert_eq!(
+            "D Y".parse::<Round>(),
+            Err(ParseRoundError::InvalidHand("D".to_string()))
+        );
+        assert_eq!(
+            "A B".parse::<Round>(),
+            Err(ParseRoundError::InvalidCode("B".to_string()))
+        );
+        assert_eq!(
+            "A Y Z".parse::<Round>(),
+            Err(ParseRoundError::InvalidRound("A Y Z".to_string()))
+        );
+        assert_eq!(
+            parse_guide("A Y\n\nB"),
+            Err((3, ParseRoundError::InvalidRound("B".to_string())))
+        );
+    }
+}
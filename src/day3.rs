@@ -1,54 +1,216 @@
-mod input;
+//! Day 3: Rucksack Reorganization
 
-fn get_prio(c: char) -> u32 {
-    match c.is_lowercase() {
-        true => c as u32 - 96,
-        false => c as u32 - 38,
+use crate::Solution;
+use std::{
+    fmt,
+    ops::{BitAnd, BitOr},
+    str::FromStr,
+};
+
+/// Priority of an item: `a`-`z` are 1-26, `A`-`Z` are 27-52. Other characters are no items.
+pub fn get_prio(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 27),
+        _ => None,
     }
 }
 
-fn part1(inp: &str) -> u32 {
-    inp.to_string().lines().fold(0, |score, line| {
-        let (left, right) = line.split_at(line.len() / 2);
-        for c in left.chars() {
-            if right.contains(c) {
-                return score + get_prio(c);
+/// Errors for a rucksack of the list.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RucksackError {
+    /// Items are letters only.
+    InvalidItem(char),
+    /// Both compartments hold the same number of items.
+    OddLength(usize),
+    /// The rucksacks cannot be split into groups of the given size.
+    IncompleteGroup { rucksacks: usize, group: usize },
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RucksackError::InvalidItem(c) => write!(f, "Invalid item {:?}", c),
+            RucksackError::OddLength(len) => {
+                write!(f, "Odd number of items ({}), the compartments differ", len)
             }
-        }
-        score
-    })
-}
-
-fn part2(inp: &str) -> u32 {
-    let mut score: u32 = 0;
-    let inp_string = inp.to_string();
-    let lines: Vec<&str> = inp_string.lines().collect();
-    for i in 0..(lines.len() / 3) {
-        let elve1 = lines[i * 3];
-        let elve2 = lines[i * 3 + 1];
-        let elve3 = lines[i * 3 + 2];
-
-        for c in elve1.chars() {
-            if elve2.contains(c) && elve3.contains(c) {
-                score += get_prio(c);
-                break;
+            RucksackError::IncompleteGroup { rucksacks, group } => {
+                write!(f, "{} rucksacks cannot be split into groups of {}", rucksacks, group)
             }
         }
     }
+}
+
+impl std::error::Error for RucksackError {}
+
+/// A set of item types, bit `priority - 1` is set for every contained type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Items(u64);
+
+impl Items {
+    pub fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        get_prio(c).is_some_and(|prio| self.0 & 1 << (prio - 1) != 0)
+    }
+
+    /// Priorities of the contained item types, in ascending order.
+    pub fn priorities(&self) -> impl Iterator<Item = u32> {
+        let bits = self.0;
+        (1..=52).filter(move |prio| bits & 1 << (prio - 1) != 0)
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl FromStr for Items {
+    type Err = RucksackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars().try_fold(Items::default(), |items, c| {
+            let prio = get_prio(c).ok_or(RucksackError::InvalidItem(c))?;
+            Ok(Items(items.0 | 1 << (prio - 1)))
+        })
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, other: Items) -> Items {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Items;
+
+    fn bitor(self, other: Items) -> Items {
+        self.union(other)
+    }
+}
+
+/// A rucksack with its two equally sized compartments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    pub left: Items,
+    pub right: Items,
+}
+
+impl Rucksack {
+    /// All item types in the rucksack.
+    pub fn items(&self) -> Items {
+        self.left | self.right
+    }
+
+    /// Item types packed into both compartments.
+    pub fn misplaced(&self) -> Items {
+        self.left & self.right
+    }
+}
 
-    score
+impl FromStr for Rucksack {
+    type Err = RucksackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Invalid characters are reported before the length, they may be what makes it odd
+        s.parse::<Items>()?;
+        if !s.len().is_multiple_of(2) {
+            return Err(RucksackError::OddLength(s.len()));
+        }
+        let (left, right) = s.split_at(s.len() / 2);
+
+        Ok(Rucksack {
+            left: left.parse()?,
+            right: right.parse()?,
+        })
+    }
 }
 
-fn main() {
-    let input = input::get_input(3);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+/// Parses the list of rucksacks, errors name the 1-based line.
+pub fn parse_rucksacks(input: &str) -> Result<Vec<Rucksack>, (usize, RucksackError)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e| (i + 1, e)))
+        .collect()
+}
+
+/// The item types carried by every rucksack of each group of `size` consecutive rucksacks.
+pub fn badges(rucksacks: &[Rucksack], size: usize) -> Result<Vec<Items>, RucksackError> {
+    if size == 0 || !rucksacks.len().is_multiple_of(size) {
+        return Err(RucksackError::IncompleteGroup {
+            rucksacks: rucksacks.len(),
+            group: size,
+        });
+    }
+
+    Ok(rucksacks
+        .chunks(size)
+        .map(|group| {
+            group
+                .iter()
+                .map(Rucksack::items)
+                .reduce(Items::intersection)
+                .unwrap_or_default()
+        })
+        .collect())
+}
+
+fn part1(rucksacks: &[Rucksack]) -> u32 {
+    rucksacks.iter().map(|r| r.misplaced().priority_sum()).sum()
+}
+
+fn part2(rucksacks: &[Rucksack]) -> u32 {
+    badges(rucksacks, 3)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .iter()
+        .map(Items::priority_sum)
+        .sum()
+}
+
+/// Priorities of the items in both compartments, and of the badges of each group of three.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<Rucksack>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        parse_rucksacks(input).map_err(|(line, e)| format!("Line {}: {}", line, e).into())
+    }
+
+    fn part1(rucksacks: &Self::Input<'_>) -> u32 {
+        part1(rucksacks)
+    }
+
+    fn part2(rucksacks: &Self::Input<'_>) -> u32 {
+        part2(rucksacks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = 
+    use crate::test_rng::Lcg;
+    const INPUT: &str =
 "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
@@ -58,11 +220,136 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part1(INPUT), 157);
+        assert_eq!(part1(&parse_rucksacks(INPUT).unwrap()), 157);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part2(INPUT), 70);
+        assert_eq!(part2(&parse_rucksacks(INPUT).unwrap()), 70);
+    }
+
+    #[test]
+    fn test_items() {
+        let rucksack: Rucksack = "vJrwpWtwJgWrhcsFMMfFFhFp".parse().unwrap();
+        assert_eq!(rucksack.misplaced().priorities().collect::<Vec<u32>>(), [16]);
+        assert!(rucksack.misplaced().contains('p'));
+        assert!(!rucksack.misplaced().contains('P'));
+        assert_eq!(rucksack.items().len(), 14);
+        assert_eq!(get_prio('L'), Some(38));
+        assert_eq!(get_prio('1'), None);
+    }
+
+    #[test]
+    fn test_badges() {
+        let rucksacks = parse_rucksacks(INPUT).unwrap();
+        let badges_of = |size| -> Vec<Vec<u32>> {
+            badges(&rucksacks, size)
+                .unwrap()
+                .iter()
+                .map(|items| items.priorities().collect())
+                .collect()
+        };
+        assert_eq!(badges_of(3), [vec![18], vec![52]]);
+        assert!(badges(&rucksacks, 6).unwrap()[0].is_empty());
+        assert_eq!(badges_of(1).len(), 6);
+        assert_eq!(
+            badges(&rucksacks, 4),
+            Err(RucksackError::IncompleteGroup {
+                rucksacks: 6,
+                group: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse_rucksacks("abAB\nab1B"),
+            Err((2, RucksackError::InvalidItem('1')))
+        );
+        assert_eq!(parse_rucksacks("abc"), Err((1, RucksackError::OddLength(3))));
     }
-}
\ No newline at end of file
+
+    // The previous string based implementation
+    fn naive_part1(inp: &str) -> u32 {
+        inp.lines().fold(0, |score, line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            for c in left.chars() {
+                if right.contains(c) {
+                    return score + get_prio(c).unwrap();
+                }
+            }
+            score
+        })
+    }
+
+    fn naive_part2(inp: &str) -> u32 {
+        let lines: Vec<&str> = inp.lines().collect();
+        let mut score = 0;
+        for group in lines.chunks(3) {
+            for c in group[0].chars() {
+                if group[1].contains(c) && group[2].contains(c) {
+                    score += get_prio(c).unwrap();
+                    break;
+                }
+            }
+        }
+        score
+    }
+
+    // Random lists with one misplaced item per rucksack and one badge per group, as the
+    // puzzle guarantees
+    fn random_input(seed: u64, groups: usize) -> String {
+        let mut rng = Lcg::new(seed);
+        let mut next = move |n: usize| rng.below(n);
+        let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+
+        let mut lines = Vec::new();
+        for _ in 0..groups {
+            let mut pool = letters.clone();
+            for i in (1..pool.len()).rev() {
+                pool.swap(i, next(i + 1));
+            }
+            let badge = pool[51];
+
+            // Every elf gets its own letters, half of them for each compartment
+            for elf in 0..3 {
+                let own = &pool[elf * 16..elf * 16 + 16];
+                let misplaced = own[next(16)];
+                let (left_pool, right_pool) = own.split_at(8);
+                let len = 1 + next(10);
+
+                let mut left: Vec<char> = (0..len).map(|_| left_pool[next(8)]).collect();
+                let mut right: Vec<char> = (0..len).map(|_| right_pool[next(8)]).collect();
+                left[next(len)] = misplaced;
+                right[next(len)] = misplaced;
+                // The badge may only be in one compartment, or it would be misplaced too
+                let side = if next(2) == 0 { &mut left } else { &mut right };
+                let free = side.iter().position(|&c| c != misplaced);
+                match free {
+                    Some(i) => side[i] = badge,
+                    None => {
+                        left.push(badge);
+                        right.push(right_pool[0]);
+                    }
+                }
+
+                lines.push(left.into_iter().chain(right).collect::<String>());
+            }
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_agrees_with_naive() {
+        assert_eq!(naive_part1(INPUT), 157);
+        assert_eq!(naive_part2(INPUT), 70);
+
+        for seed in 0..100 {
+            let input = random_input(seed, 1 + seed as usize % 5);
+            let rucksacks = parse_rucksacks(&input).unwrap();
+            assert_eq!(part1(&rucksacks), naive_part1(&input), "{}", input);
+            assert_eq!(part2(&rucksacks), naive_part2(&input), "{}", input);
+        }
+    }
+}
@@ -1,63 +1,89 @@
-mod input;
-
-fn parse_input(input: &str) -> Vec<(usize, usize, usize, usize)> {
-    input.lines().map(|line| {
-        let parts = line.split(',').flat_map(|side| {
-            side.split('-').map(|s| s.parse::<usize>().unwrap()).collect::<Vec<usize>>()
-        }).collect::<Vec<usize>>();
-        assert!(parts.len() == 4, "Invalid input");
-        (parts[0], parts[1], parts[2], parts[3])
-    }).collect()
+//! Day 4: Camp Cleanup
+
+use std::fmt;
+
+use crate::{
+    interval::{ParseSectionError, Section},
+    Solution,
+};
+
+/// Errors for a line of the assignment list.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AssignmentError {
+    /// The line is not two sections separated by a comma.
+    MissingComma(String),
+    Section(ParseSectionError),
 }
 
-fn is_contained(start1: &usize, end1: &usize, start2: &usize, end2: &usize) -> bool {
-    if start1 >= start2 && end1 <= end2 {
-        return true;
-    }
-    if start2 >= start1 && end2 <= end1 {
-        return true;
+impl fmt::Display for AssignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssignmentError::MissingComma(s) => write!(f, "Expected two sections, got {:?}", s),
+            AssignmentError::Section(e) => e.fmt(f),
+        }
     }
-    false
 }
 
-fn does_overlap(start1: &usize, end1: &usize, start2: &usize, end2: &usize) -> bool {
-    if start1 >= start2 && start1 <= end2 {
-        return true;
-    }
-    if start2 >= start1 && start2 <= end1 {
-        return true;
+impl std::error::Error for AssignmentError {}
+
+impl From<ParseSectionError> for AssignmentError {
+    fn from(e: ParseSectionError) -> Self {
+        AssignmentError::Section(e)
     }
-    false
 }
 
-fn part1(inp: &[(usize, usize, usize, usize)]) -> u32 {
-    inp.iter().fold(0, |acc, (start1, end1, start2, end2)| {
-        if is_contained(start1, end1, start2, end2) {
-            return acc + 1;
-        }
-        acc
-    })
+fn parse_pair(line: &str) -> Result<(Section, Section), AssignmentError> {
+    let (first, second) = line
+        .split_once(',')
+        .ok_or_else(|| AssignmentError::MissingComma(line.to_string()))?;
+    Ok((first.parse()?, second.parse()?))
 }
 
-fn part2(inp: &[(usize, usize, usize, usize)]) -> u32 {
-    inp.iter().fold(0, |acc, (start1, end1, start2, end2)| {
-        if does_overlap(start1, end1, start2, end2) {
-            return acc + 1;
-        }
-        acc
-    })
+/// Parses each line into the pair of assignments, errors name the 1-based line.
+pub fn parse_input(input: &str) -> Result<Vec<(Section, Section)>, (usize, AssignmentError)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_pair(line).map_err(|e| (i + 1, e)))
+        .collect()
+}
+
+fn part1(pairs: &[(Section, Section)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(a, b)| a.contains_section(b) || b.contains_section(a))
+        .count()
 }
 
-fn main() {
-    let input = input::get_input(4);
-    let parsed_input = parse_input(&input);
-    println!("Part 1: {}", part1(&parsed_input));
-    println!("Part 2: {}", part2(&parsed_input));
+fn part2(pairs: &[(Section, Section)]) -> usize {
+    pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
+}
+
+/// Number of pairs where one assignment contains the other, and where they overlap at all.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<(Section, Section)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        parse_input(input).map_err(|(line, e)| format!("Line {}: {}", line, e).into())
+    }
+
+    fn part1(pairs: &Self::Input<'_>) -> usize {
+        part1(pairs)
+    }
+
+    fn part2(pairs: &Self::Input<'_>) -> usize {
+        part2(pairs)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interval::IntervalSet;
     const INPUT: &str =
 "2-4,6-8
 2-3,4-5
@@ -68,15 +94,43 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let parsed_input = parse_input(INPUT);
+        let parsed_input = parse_input(INPUT).unwrap();
         let result = part1(&parsed_input);
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_part_2() {
-        let parsed_input = parse_input(INPUT);
+        let parsed_input = parse_input(INPUT).unwrap();
         let result = part2(&parsed_input);
         assert_eq!(result, 4);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_coverage() {
+        let sections: Vec<Section> = parse_input(INPUT)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(a, b)| [a, b])
+            .collect();
+        let covered: IntervalSet = sections.iter().copied().collect();
+
+        assert_eq!(covered.gaps(&Section::new(1, 9).unwrap()), [Section::new(1, 1).unwrap()]);
+        assert_eq!(
+            IntervalSet::covered_more_than(&sections, 6).sections(),
+            [Section::new(4, 6).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("2-4,6-8\n2-3"),
+            Err((2, AssignmentError::MissingComma("2-3".to_string())))
+        );
+        assert!(matches!(
+            parse_input("2-4,8-6"),
+            Err((1, AssignmentError::Section(ParseSectionError::Empty(_))))
+        ));
+    }
+}
@@ -1,103 +1,633 @@
-mod input;
+//! Day 5: Supply Stacks
 
-type Stacks = Vec<Vec<char>>;
+use crate::Solution;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
-#[derive(Debug)]
-struct Instruction {
-    from: u8,
-    to: u8,
-    times: u8
+/// Stacks of crates with their labels, the last crate of each stack is the top one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stacks {
+    pub labels: Vec<String>,
+    pub crates: Vec<Vec<char>>,
 }
 
-fn parse_input(inp: &str) -> (Stacks, Vec<Instruction>) {
-    let inp_string = inp.to_string();
-    let mut parts = inp_string.split("\n\n").collect::<Vec<&str>>();
-    let instruction_str = parts.pop().unwrap();
-    let configuration_str = parts.pop().unwrap();
-    
-    let stack_number = (configuration_str.split_once('\n').unwrap().0.len()+1)/4;
-    let mut stacks: Stacks = Vec::with_capacity(stack_number);
-    for _ in 0..stack_number {
-        stacks.push(Vec::new());
+impl Stacks {
+    /// Stacks labelled 1, 2, 3 ... in order, crates listed from the bottom.
+    pub fn new(crates: Vec<Vec<char>>) -> Stacks {
+        Stacks {
+            labels: (1..=crates.len()).map(|i| i.to_string()).collect(),
+            crates,
+        }
     }
 
-    println!("configuration_str:\n{}", configuration_str);
-    println!("number of stacks: {}", stack_number);
+    /// Number of the stack with the given label, stacks are numbered from 1.
+    pub fn number(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|l| l == label).map(|i| i + 1)
+    }
 
-    for line in configuration_str.lines().rev() {
-        for (i, stack) in stacks.iter_mut().enumerate() {
-            let letter = line.chars().nth(i*4+1).unwrap();
-            if !letter.is_whitespace() && !letter.is_ascii_digit() {
-                stack.push(letter);
-            }
+    // Every column is as wide as its label, but at least as wide as a crate
+    fn column_widths(&self) -> Vec<usize> {
+        self.labels
+            .iter()
+            .map(|label| label.chars().count().max(3))
+            .collect()
+    }
+}
+
+/// Renders the stacks as in the puzzle input, labels below the crates:
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// Every line spans all columns, including trailing spaces, as in the puzzle input.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths = self.column_widths();
+        let height = self.crates.iter().map(Vec::len).max().unwrap_or(0);
+
+        for level in (0..height).rev() {
+            let cells: Vec<String> = self
+                .crates
+                .iter()
+                .zip(&widths)
+                .map(|(stack, &width)| match stack.get(level) {
+                    Some(c) => center(&format!("[{}]", c), width),
+                    None => " ".repeat(width),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
         }
+
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .zip(&widths)
+            .map(|(label, &width)| center(label, width))
+            .collect();
+        write!(f, "{}", labels.join(" "))
     }
+}
+
+fn center(text: &str, width: usize) -> String {
+    let padding = width - text.chars().count();
+    let left = padding / 2;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(padding - left))
+}
+
+/// A `move <times> from <from> to <to>` step, stacks are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub from: usize,
+    pub to: usize,
+    pub times: usize,
+}
 
-    let instructions = instruction_str.lines().map(|line|{
-        let parts = line.split_whitespace().collect::<Vec<&str>>();
-        Instruction {
-            from: parts[3].parse().unwrap(),
-            to: parts[5].parse().unwrap(),
-            times: parts[1].parse().unwrap()
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.times, self.from, self.to)
+    }
+}
+
+/// What is wrong with a line of the input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The drawing has no line with the stack labels.
+    MissingLabels,
+    DuplicateLabel(String),
+    /// Something other than `[X]` in a line of crates.
+    InvalidCrate(String),
+    /// A crate that is not above any label.
+    CrateOutsideStacks(usize),
+    /// A crate with an empty space below it.
+    FloatingCrate(String),
+    InvalidInstruction(String),
+    UnknownStack(String),
+}
+
+/// Error with the 1-based line of the input it was found in.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+        match &self.kind {
+            ParseErrorKind::MissingLabels => write!(f, "the drawing has no stack labels"),
+            ParseErrorKind::DuplicateLabel(label) => write!(f, "stack {} is labelled twice", label),
+            ParseErrorKind::InvalidCrate(text) => write!(f, "invalid crate {:?}", text),
+            ParseErrorKind::CrateOutsideStacks(column) => {
+                write!(f, "the crate in column {} is not above a stack label", column)
+            }
+            ParseErrorKind::FloatingCrate(label) => {
+                write!(f, "a crate of stack {} is floating above an empty space", label)
+            }
+            ParseErrorKind::InvalidInstruction(text) => {
+                write!(f, "expected move <n> from <stack> to <stack>, got {:?}", text)
+            }
+            ParseErrorKind::UnknownStack(label) => write!(f, "there is no stack {}", label),
         }
-    }).collect::<Vec<Instruction>>();
+    }
+}
 
-    (stacks, instructions)
+impl std::error::Error for ParseError {}
+
+fn error(line: usize, kind: ParseErrorKind) -> ParseError {
+    ParseError { line, kind }
+}
+
+/// Parses the drawing of the starting stacks and the rearrangement procedure.
+///
+/// The drawing ends at the first empty line. Lines may be trimmed or use CRLF, stack labels may
+/// be longer than one character. Instructions refer to the stacks by their labels.
+pub fn parse_input(inp: &str) -> Result<(Stacks, Vec<Instruction>), ParseError> {
+    // `lines` also strips the `\r` of CRLF line endings
+    let lines: Vec<&str> = inp.lines().collect();
+    let split = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or(lines.len());
+
+    let stacks = parse_stacks(&lines[..split])?;
+    let instructions = lines
+        .iter()
+        .enumerate()
+        .skip(split)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_instruction(line, &stacks).map_err(|kind| error(i + 1, kind)))
+        .collect::<Result<Vec<Instruction>, ParseError>>()?;
+
+    Ok((stacks, instructions))
 }
 
-fn get_keyword(stacks: &Stacks) -> String {
-    let mut word = String::new();
-    for stack in stacks {
-        word.push(*stack.last().unwrap());
+/// Parses the drawing, the last line holds the labels of the stacks.
+pub fn parse_stacks(lines: &[&str]) -> Result<Stacks, ParseError> {
+    let (label_line, crate_lines) = lines
+        .split_last()
+        .ok_or_else(|| error(1, ParseErrorKind::MissingLabels))?;
+    let label_number = lines.len();
+
+    // Each label covers the characters it is written in
+    let mut labels = Vec::new();
+    let mut spans = Vec::new();
+    for (start, label) in tokens(label_line) {
+        if label.starts_with('[') {
+            return Err(error(label_number, ParseErrorKind::MissingLabels));
+        }
+        if labels.contains(&label.to_string()) {
+            return Err(error(label_number, ParseErrorKind::DuplicateLabel(label.to_string())));
+        }
+        labels.push(label.to_string());
+        spans.push((start, start + label.chars().count() - 1));
+    }
+    if labels.is_empty() {
+        return Err(error(label_number, ParseErrorKind::MissingLabels));
+    }
+
+    let mut crates = vec![Vec::new(); labels.len()];
+    // Stacks that had an empty space on a lower line
+    let mut ended = HashSet::new();
+
+    for (i, line) in crate_lines.iter().enumerate().rev() {
+        let line_number = i + 1;
+        let mut filled = HashSet::new();
+
+        for (start, token) in tokens(line) {
+            let chars: Vec<char> = token.chars().collect();
+            let letter = match chars[..] {
+                ['[', c, ']'] if !c.is_whitespace() => c,
+                _ => {
+                    return Err(error(line_number, ParseErrorKind::InvalidCrate(token.to_string())))
+                }
+            };
+
+            // The crate belongs to the label it overlaps, e.g. `[A]` above ` 1 ` or `12`
+            let end = start + 2;
+            let stack = spans
+                .iter()
+                .position(|&(label_start, label_end)| start <= label_end && label_start <= end)
+                .ok_or_else(|| error(line_number, ParseErrorKind::CrateOutsideStacks(start + 1)))?;
+
+            if ended.contains(&stack) {
+                return Err(error(
+                    line_number,
+                    ParseErrorKind::FloatingCrate(labels[stack].clone()),
+                ));
+            }
+            crates[stack].push(letter);
+            filled.insert(stack);
+        }
+
+        ended.extend((0..labels.len()).filter(|stack| !filled.contains(stack)));
     }
-    word
+
+    Ok(Stacks { labels, crates })
 }
 
-fn part_1(stacks: &Stacks, instructions: &Vec<Instruction>) -> String {
-    let mut stacks = stacks.clone();
+// Whitespace separated tokens with the character index they start at. Adjacent crates like
+// `[A][B]` are split as well.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
 
-    for instruction in instructions {
-        for _ in 0..instruction.times {
-            let letter = stacks[(instruction.from-1) as usize].pop().unwrap();
-            stacks[(instruction.to-1) as usize].push(letter);
+    for (column, &(byte, c)) in chars.iter().enumerate() {
+        let splits_crates = c == '[' && column > 0 && chars[column - 1].1 == ']';
+        if let Some((token_column, token_byte)) = start {
+            if c.is_whitespace() || splits_crates {
+                tokens.push((token_column, &line[token_byte..byte]));
+                start = None;
+            }
         }
+        if start.is_none() && !c.is_whitespace() {
+            start = Some((column, byte));
+        }
+    }
+    if let Some((token_column, token_byte)) = start {
+        tokens.push((token_column, &line[token_byte..]));
+    }
+
+    tokens
+}
+
+fn parse_instruction(line: &str, stacks: &Stacks) -> Result<Instruction, ParseErrorKind> {
+    let invalid = || ParseErrorKind::InvalidInstruction(line.to_string());
+    let stack = |label: &str| {
+        stacks
+            .number(label)
+            .ok_or_else(|| ParseErrorKind::UnknownStack(label.to_string()))
     };
 
-    get_keyword(&stacks)
+    match line.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["move", times, "from", from, "to", to] => Ok(Instruction {
+            times: times.parse().map_err(|_| invalid())?,
+            from: stack(from)?,
+            to: stack(to)?,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+/// The crates on top of each stack, empty stacks are skipped.
+pub fn get_keyword(stacks: &Stacks) -> String {
+    stacks.crates.iter().filter_map(|stack| stack.last()).collect()
+}
+
+/// A move that cannot be made with the current stacks.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// Stacks are numbered from 1 to the number of stacks.
+    NoSuchStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        crates: usize,
+        times: usize,
+    },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::NoSuchStack(stack) => write!(f, "there is no stack {}", stack),
+            MoveError::NotEnoughCrates {
+                stack,
+                crates,
+                times,
+            } => write!(
+                f,
+                "cannot move {} crates from stack {}, it holds {}",
+                times, stack, crates
+            ),
+        }
+    }
 }
 
-fn part_2(stacks: &Stacks, instructions: &Vec<Instruction>) -> String {
+impl std::error::Error for MoveError {}
+
+/// A crane model, deciding in which order the moved crates end up on the target stack.
+pub trait Crane {
+    /// Moves the top `times` crates of `from` onto `to`, `from` holds at least `times` crates.
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, times: usize);
+
+    /// Carries out one instruction, the stacks are left unchanged if it is invalid.
+    fn apply(&self, stacks: &mut Stacks, instruction: &Instruction) -> Result<(), MoveError> {
+        let Instruction { from, to, times } = *instruction;
+        for stack in [from, to] {
+            if stack == 0 || stack > stacks.crates.len() {
+                return Err(MoveError::NoSuchStack(stack));
+            }
+        }
+        let crates = stacks.crates[from - 1].len();
+        if crates < times {
+            return Err(MoveError::NotEnoughCrates {
+                stack: from,
+                crates,
+                times,
+            });
+        }
+
+        // Putting crates back onto the stack they came from changes nothing, for any crane
+        if from != to {
+            let mut source = std::mem::take(&mut stacks.crates[from - 1]);
+            self.lift(&mut source, &mut stacks.crates[to - 1], times);
+            stacks.crates[from - 1] = source;
+        }
+        Ok(())
+    }
+}
+
+/// Moves one crate at a time, reversing the order of the moved crates.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, times: usize) {
+        let start = from.len() - times;
+        to.extend(from.drain(start..).rev());
+    }
+}
+
+/// Moves all crates of an instruction at once, keeping their order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, times: usize) {
+        let start = from.len() - times;
+        to.extend(from.drain(start..));
+    }
+}
+
+/// Lifts at most `capacity` crates at once, larger moves are split into several lifts that each
+/// keep their order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitedCrane {
+    capacity: usize,
+}
+
+impl LimitedCrane {
+    /// A crane lifting up to `capacity` crates, `None` if it cannot lift any.
+    pub fn new(capacity: usize) -> Option<LimitedCrane> {
+        (capacity > 0).then_some(LimitedCrane { capacity })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl Crane for LimitedCrane {
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, times: usize) {
+        let mut left = times;
+        while left > 0 {
+            let lifted = left.min(self.capacity);
+            CrateMover9001.lift(from, to, lifted);
+            left -= lifted;
+        }
+    }
+}
+
+/// The stacks after each instruction, see [`replay`].
+pub struct Steps<'a> {
+    crane: &'a dyn Crane,
+    stacks: Stacks,
+    instructions: std::iter::Enumerate<std::slice::Iter<'a, Instruction>>,
+}
+
+impl Iterator for Steps<'_> {
+    /// The stacks after the instruction, or the 1-based number of the invalid instruction.
+    type Item = Result<Stacks, (usize, MoveError)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, instruction) = self.instructions.next()?;
+        match self.crane.apply(&mut self.stacks, instruction) {
+            Ok(()) => Some(Ok(self.stacks.clone())),
+            Err(e) => {
+                // Later instructions would start from the wrong stacks
+                self.instructions = [].iter().enumerate();
+                Some(Err((i + 1, e)))
+            }
+        }
+    }
+}
+
+/// Carries out the instructions one by one, yielding the stacks after each of them. Stops after
+/// the first invalid instruction.
+pub fn replay<'a>(
+    crane: &'a dyn Crane,
+    stacks: &Stacks,
+    instructions: &'a [Instruction],
+) -> Steps<'a> {
+    Steps {
+        crane,
+        stacks: stacks.clone(),
+        instructions: instructions.iter().enumerate(),
+    }
+}
+
+/// The stacks after all instructions, or the 1-based number of the first invalid instruction.
+pub fn rearrange(
+    crane: &dyn Crane,
+    stacks: &Stacks,
+    instructions: &[Instruction],
+) -> Result<Stacks, (usize, MoveError)> {
     let mut stacks = stacks.clone();
-    for instruction in instructions {
-        let mut buffer = Vec::new();
-        for _ in 0..instruction.times {
-            let letter = stacks[(instruction.from-1) as usize].pop().unwrap();
-            buffer.push(letter);
+    for (i, instruction) in instructions.iter().enumerate() {
+        crane.apply(&mut stacks, instruction).map_err(|e| (i + 1, e))?;
+    }
+    Ok(stacks)
+}
+
+/// What [`plan`] should arrange the stacks into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// Stacks whose top crates spell the keyword, see [`get_keyword`].
+    Keyword(String),
+    /// Exactly these crates on every stack, labels are ignored.
+    Stacks(Stacks),
+}
+
+impl Target {
+    fn is_reached(&self, crates: &[Vec<char>]) -> bool {
+        match self {
+            Target::Keyword(keyword) => crates
+                .iter()
+                .filter_map(|stack| stack.last().copied())
+                .eq(keyword.chars()),
+            Target::Stacks(target) => target.crates == crates,
         }
+    }
+}
 
-        buffer.reverse();
+/// Why no instructions were found for a target.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PlanError {
+    /// The target has a different number of stacks.
+    StackCount { stacks: usize, target: usize },
+    /// The target needs crates the stacks do not hold.
+    MissingCrates,
+    /// No sequence of moves of the crane reaches the target.
+    Unreachable,
+    /// More than the given number of configurations were explored.
+    LimitReached(usize),
+}
 
-        for letter in buffer {
-            stacks[(instruction.to-1) as usize].push(letter);
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanError::StackCount { stacks, target } => {
+                write!(f, "the target has {} stacks instead of {}", target, stacks)
+            }
+            PlanError::MissingCrates => write!(f, "the target needs crates that are not there"),
+            PlanError::Unreachable => write!(f, "the crane cannot reach the target"),
+            PlanError::LimitReached(limit) => {
+                write!(f, "no solution within {} configurations", limit)
+            }
         }
-    };
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+// Whether every crate of `needed` can be taken from `available`, counting duplicates
+fn holds_crates<'a>(
+    available: impl Iterator<Item = &'a char>,
+    mut needed: impl Iterator<Item = &'a char>,
+) -> bool {
+    let mut counts = HashMap::new();
+    for c in available {
+        *counts.entry(*c).or_insert(0) += 1;
+    }
+    needed.all(|c| {
+        let count = counts.entry(*c).or_insert(0);
+        *count -= 1;
+        *count >= 0
+    })
+}
 
+/// Finds a shortest list of instructions that arranges `stacks` into `target` with the crane.
+///
+/// Searches breadth first over the configurations of the stacks, giving up after `limit`
+/// distinct configurations.
+pub fn plan(
+    crane: &dyn Crane,
+    stacks: &Stacks,
+    target: &Target,
+    limit: usize,
+) -> Result<Vec<Instruction>, PlanError> {
+    let crates = || stacks.crates.iter().flatten();
+    match target {
+        Target::Keyword(keyword) => {
+            let keyword: Vec<char> = keyword.chars().collect();
+            if keyword.len() > stacks.crates.len() || !holds_crates(crates(), keyword.iter()) {
+                return Err(PlanError::MissingCrates);
+            }
+        }
+        Target::Stacks(target) => {
+            if target.crates.len() != stacks.crates.len() {
+                return Err(PlanError::StackCount {
+                    stacks: stacks.crates.len(),
+                    target: target.crates.len(),
+                });
+            }
+            let needed = || target.crates.iter().flatten();
+            if !holds_crates(crates(), needed()) || !holds_crates(needed(), crates()) {
+                return Err(PlanError::MissingCrates);
+            }
+        }
+    }
+
+    // Configurations in the order they were found, with the one they were reached from. The
+    // list doubles as the queue of the search.
+    let mut found = vec![(stacks.crates.clone(), None)];
+    let mut seen = HashSet::from([stacks.crates.clone()]);
+    let mut next = 0;
+
+    while let Some((crates, _)) = found.get(next) {
+        if target.is_reached(crates) {
+            let mut instructions = Vec::new();
+            let mut current = next;
+            while let Some((_, Some((previous, instruction)))) = found.get(current) {
+                instructions.push(*instruction);
+                current = *previous;
+            }
+            instructions.reverse();
+            return Ok(instructions);
+        }
+
+        let mut moves = Vec::new();
+        for from in 1..=crates.len() {
+            for to in (1..=crates.len()).filter(|&to| to != from) {
+                for times in 1..=crates[from - 1].len() {
+                    let mut moved = crates.clone();
+                    let mut source = std::mem::take(&mut moved[from - 1]);
+                    crane.lift(&mut source, &mut moved[to - 1], times);
+                    moved[from - 1] = source;
+                    moves.push((moved, Instruction { from, to, times }));
+                }
+            }
+        }
+
+        for (moved, instruction) in moves {
+            if seen.insert(moved.clone()) {
+                if found.len() == limit {
+                    return Err(PlanError::LimitReached(limit));
+                }
+                found.push((moved, Some((next, instruction))));
+            }
+        }
+        next += 1;
+    }
+
+    Err(PlanError::Unreachable)
+}
+
+fn top_crates(crane: &dyn Crane, stacks: &Stacks, instructions: &[Instruction]) -> String {
+    let stacks = rearrange(crane, stacks, instructions)
+        .unwrap_or_else(|(step, e)| panic!("Step {}: {}", step, e));
     get_keyword(&stacks)
 }
 
-fn main() {
-    let input = input::get_input(5);
-    let (crates, instructions) = parse_input(&input);
-    println!("Part 1: {}", part_1(&crates, &instructions));
-    println!("Part 2: {}", part_2(&crates, &instructions));
+fn part_1(stacks: &Stacks, instructions: &[Instruction]) -> String {
+    top_crates(&CrateMover9000, stacks, instructions)
+}
 
+fn part_2(stacks: &Stacks, instructions: &[Instruction]) -> String {
+    top_crates(&CrateMover9001, stacks, instructions)
+}
+
+/// Top crates after moving one crate at a time, and after moving all crates of a step at once.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = (Stacks, Vec<Instruction>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1((crates, instructions): &Self::Input<'_>) -> String {
+        part_1(crates, instructions)
+    }
+
+    fn part2((crates, instructions): &Self::Input<'_>) -> String {
+        part_2(crates, instructions)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = 
+    const INPUT: &str =
 "    [D]    
 [N] [C]    
 [Z] [M] [P]
@@ -110,13 +640,228 @@ move 1 from 1 to 2";
 
     #[test]
     fn part_one() {
-        let (crates, instructions) = parse_input(INPUT);
+        let (crates, instructions) = parse_input(INPUT).unwrap();
         assert_eq!(part_1(&crates, &instructions), "CMZ");
     }
 
     #[test]
     fn part_two() {
-        let (crates, instructions) = parse_input(INPUT);
+        let (crates, instructions) = parse_input(INPUT).unwrap();
         assert_eq!(part_2(&crates, &instructions), "MCD");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cranes() {
+        let (stacks, instructions) = parse_input(INPUT).unwrap();
+        let keyword =
+            |crane: &dyn Crane| get_keyword(&rearrange(crane, &stacks, &instructions).unwrap());
+
+        assert_eq!(keyword(&LimitedCrane::new(1).unwrap()), "CMZ");
+        assert_eq!(keyword(&LimitedCrane::new(3).unwrap()), "MCD");
+        assert!(LimitedCrane::new(0).is_none());
+
+        // Three crates lifted two at a time
+        let mut stacks = Stacks::new(vec![vec!['A', 'B', 'C'], vec![]]);
+        let instruction = Instruction {
+            from: 1,
+            to: 2,
+            times: 3,
+        };
+        LimitedCrane::new(2).unwrap().apply(&mut stacks, &instruction).unwrap();
+        assert_eq!(stacks.crates, [vec![], vec!['B', 'C', 'A']]);
+    }
+
+    #[test]
+    fn test_replay() {
+        let (stacks, instructions) = parse_input(INPUT).unwrap();
+        let steps: Vec<Stacks> = replay(&CrateMover9000, &stacks, &instructions)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[0].crates, [vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']]);
+        assert_eq!(steps[3], rearrange(&CrateMover9000, &stacks, &instructions).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_moves() {
+        let (stacks, _) = parse_input(INPUT).unwrap();
+        let instructions = [
+            Instruction {
+                from: 3,
+                to: 1,
+                times: 1,
+            },
+            Instruction {
+                from: 3,
+                to: 1,
+                times: 1,
+            },
+            Instruction {
+                from: 4,
+                to: 1,
+                times: 1,
+            },
+        ];
+
+        let steps: Vec<_> = replay(&CrateMover9001, &stacks, &instructions).collect();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(
+            steps[1],
+            Err((
+                2,
+                MoveError::NotEnoughCrates {
+                    stack: 3,
+                    crates: 0,
+                    times: 1
+                }
+            ))
+        );
+
+        let mut unchanged = stacks.clone();
+        assert_eq!(
+            CrateMover9000.apply(&mut unchanged, &instructions[2]),
+            Err(MoveError::NoSuchStack(4))
+        );
+        assert_eq!(unchanged, stacks);
+    }
+
+    #[test]
+    fn test_plan() {
+        let (stacks, instructions) = parse_input(INPUT).unwrap();
+        let limited = LimitedCrane::new(2).unwrap();
+        let cranes: [&dyn Crane; 3] = [&CrateMover9000, &CrateMover9001, &limited];
+
+        for crane in cranes {
+            // The example instructions are one way to get there, maybe not the shortest
+            let target = rearrange(crane, &stacks, &instructions).unwrap();
+            let found = plan(crane, &stacks, &Target::Stacks(target.clone()), 100_000).unwrap();
+            assert!(found.len() <= instructions.len());
+            assert_eq!(rearrange(crane, &stacks, &found), Ok(target));
+
+            let keyword = Target::Keyword("DCP".to_string());
+            let found = plan(crane, &stacks, &keyword, 100_000).unwrap();
+            assert_eq!(found.len(), 1);
+            assert_eq!(get_keyword(&rearrange(crane, &stacks, &found).unwrap()), "DCP");
+        }
+
+        let unchanged = Target::Stacks(stacks.clone());
+        assert_eq!(plan(&CrateMover9000, &stacks, &unchanged, 1), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_plan_shortest() {
+        // Turning a stack upside down takes one move with the 9000, three with the 9001
+        let stacks = Stacks::new(vec![vec!['A', 'B', 'C'], vec![], vec![]]);
+        let target = Target::Stacks(Stacks::new(vec![vec![], vec!['C', 'B', 'A'], vec![]]));
+        assert_eq!(plan(&CrateMover9000, &stacks, &target, 1000).unwrap().len(), 1);
+        assert_eq!(plan(&CrateMover9001, &stacks, &target, 1000).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let (stacks, _) = parse_input(INPUT).unwrap();
+        let keyword = |keyword: &str| Target::Keyword(keyword.to_string());
+
+        let missing = Err(PlanError::MissingCrates);
+        assert_eq!(plan(&CrateMover9000, &stacks, &keyword("XYZ"), 1000), missing);
+        assert_eq!(plan(&CrateMover9000, &stacks, &keyword("ZZ"), 1000), missing);
+        assert_eq!(
+            plan(&CrateMover9000, &stacks, &Target::Stacks(Stacks::new(vec![vec![]])), 1000),
+            Err(PlanError::StackCount {
+                stacks: 3,
+                target: 1
+            })
+        );
+        assert_eq!(
+            plan(&CrateMover9000, &stacks, &keyword("ZMP"), 10),
+            Err(PlanError::LimitReached(10))
+        );
+
+        // Nothing can be moved with a single stack
+        let stacks = Stacks::new(vec![vec!['A', 'B']]);
+        let target = Target::Stacks(Stacks::new(vec![vec!['B', 'A']]));
+        assert_eq!(plan(&CrateMover9000, &stacks, &target, 1000), Err(PlanError::Unreachable));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (stacks, _) = parse_input(INPUT).unwrap();
+        assert_eq!(stacks, Stacks::new(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]));
+
+        let drawing = INPUT.split("\n\n").next().unwrap();
+        assert_eq!(stacks.to_string(), drawing);
+        assert_eq!(parse_stacks(&drawing.lines().collect::<Vec<&str>>()), Ok(stacks));
+    }
+
+    #[test]
+    fn test_trimmed_crlf() {
+        let input = INPUT
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<&str>>()
+            .join("\r\n");
+        assert_eq!(parse_input(&input), parse_input(INPUT));
+    }
+
+    #[test]
+    fn test_many_stacks() {
+        let crates: Vec<Vec<char>> = (0..12)
+            .map(|i| (0..i % 4).map(|j| (b'A' + (i + j) as u8) as char).collect())
+            .collect();
+        let stacks = Stacks::new(crates);
+        let drawing = stacks.to_string();
+        assert!(drawing.ends_with(" 9  10  11  12 "));
+
+        let input = format!("{}\n\nmove 2 from 12 to 10\n", drawing);
+        let (parsed, instructions) = parse_input(&input).unwrap();
+        assert_eq!(parsed, stacks);
+        assert_eq!(
+            instructions,
+            [Instruction {
+                from: 12,
+                to: 10,
+                times: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let input = "    [B]\n[A] [C]\n one two\n\nmove 1 from two to one";
+        let (stacks, instructions) = parse_input(input).unwrap();
+        assert_eq!(stacks.labels, ["one", "two"]);
+        assert_eq!(stacks.crates, [vec!['A'], vec!['C', 'B']]);
+        assert_eq!(instructions[0].from, 2);
+        assert_eq!(parse_stacks(&stacks.to_string().lines().collect::<Vec<&str>>()), Ok(stacks));
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |input: &str| parse_input(input).map(|_| ()).unwrap_err();
+        assert_eq!(
+            kind("[A] [B]\n 1   1 "),
+            error(2, ParseErrorKind::DuplicateLabel("1".to_string()))
+        );
+        assert_eq!(
+            kind("[A] (B)\n 1   2 "),
+            error(1, ParseErrorKind::InvalidCrate("(B)".to_string()))
+        );
+        assert_eq!(
+            kind("[A]     [C]\n 1   2 "),
+            error(1, ParseErrorKind::CrateOutsideStacks(9))
+        );
+        assert_eq!(
+            kind("[A]\n    [B]\n 1   2 "),
+            error(1, ParseErrorKind::FloatingCrate("1".to_string()))
+        );
+        assert_eq!(
+            kind("[A]\n 1 \n\nmove 1 from 1 to 2"),
+            error(4, ParseErrorKind::UnknownStack("2".to_string()))
+        );
+        assert_eq!(
+            kind("[A]\n 1 \n\n\nmove one from 1 to 1"),
+            error(5, ParseErrorKind::InvalidInstruction("move one from 1 to 1".to_string()))
+        );
+        assert_eq!(kind("[A]\n[B]"), error(2, ParseErrorKind::MissingLabels));
+    }
+}
@@ -1,33 +1,164 @@
-mod input;
+//! Day 6: Tuning Trouble
 
-fn has_unique_chars(word: &str) -> bool {
-    let mut chars = word.chars().collect::<Vec<char>>();
-    chars.sort();
-    chars.dedup();
-    chars.len() == word.len()
+use crate::Solution;
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    io::{self, BufReader, Bytes, Read},
+};
+
+/// Sliding window over a stream of symbols, detecting where the last `length` symbols are all
+/// different. Every symbol is handled in constant time.
+#[derive(Debug, Clone)]
+pub struct MarkerDetector<T> {
+    length: usize,
+    window: VecDeque<T>,
+    counts: HashMap<T, usize>,
+    // Symbols that appear more than once in the window
+    repeated: usize,
+    position: usize,
 }
 
-fn get_position_of_n_unique_chars(inp: &str, length: usize) -> usize {
-    assert!(length > 0 && length < inp.len(), "length must be between 1 and the length of the input");
-    for i in 0..(inp.len()-length) {
-        let sliced = &inp[i..i+length];
-        if has_unique_chars(sliced) {
-            return i + length;
+impl<T: Hash + Eq + Copy> MarkerDetector<T> {
+    /// A detector for markers of `length` symbols, `None` for markers without symbols.
+    pub fn new(length: usize) -> Option<MarkerDetector<T>> {
+        (length > 0).then(|| MarkerDetector {
+            length,
+            window: VecDeque::with_capacity(length + 1),
+            counts: HashMap::new(),
+            repeated: 0,
+            position: 0,
+        })
+    }
+
+    /// Adds the next symbol, `true` if it ends a marker.
+    pub fn push(&mut self, symbol: T) -> bool {
+        self.position += 1;
+        self.window.push_back(symbol);
+        let count = self.counts.entry(symbol).or_insert(0);
+        *count += 1;
+        if *count == 2 {
+            self.repeated += 1;
+        }
+
+        if self.window.len() > self.length {
+            let dropped = self.window.pop_front().unwrap();
+            let count = self.counts.get_mut(&dropped).unwrap();
+            *count -= 1;
+            if *count == 1 {
+                self.repeated -= 1;
+            }
+        }
+
+        self.window.len() == self.length && self.repeated == 0
+    }
+
+    /// Number of symbols pushed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Ends of all markers of `length` different characters, as the number of characters up to and
+/// including the marker.
+pub fn marker_positions(input: &str, length: usize) -> impl Iterator<Item = usize> + '_ {
+    let mut detector = MarkerDetector::new(length).expect("markers must be at least 1 long");
+    input.chars().enumerate().filter(move |&(_, c)| detector.push(c)).map(|(i, _)| i + 1)
+}
+
+/// Ends of all markers in a UTF-8 stream, read as they are needed. Every character counts,
+/// including line breaks.
+pub struct Markers<R> {
+    bytes: Bytes<BufReader<R>>,
+    detector: MarkerDetector<char>,
+}
+
+impl<R: Read> Markers<R> {
+    pub fn new(reader: R, length: usize) -> Markers<R> {
+        Markers {
+            bytes: BufReader::new(reader).bytes(),
+            detector: MarkerDetector::new(length).expect("markers must be at least 1 long"),
+        }
+    }
+
+    // The next character of the stream, decoded from up to four bytes
+    fn next_char(&mut self) -> Option<io::Result<char>> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "stream is not valid UTF-8");
+
+        let first = match self.bytes.next()? {
+            Ok(byte) => byte,
+            Err(e) => return Some(Err(e)),
+        };
+        let width = match first.leading_ones() {
+            0 => 1,
+            n @ 2..=4 => n as usize,
+            _ => return Some(Err(invalid())),
         };
+
+        let mut buffer = [first, 0, 0, 0];
+        for byte in &mut buffer[1..width] {
+            *byte = match self.bytes.next() {
+                Some(Ok(byte)) => byte,
+                Some(Err(e)) => return Some(Err(e)),
+                None => return Some(Err(invalid())),
+            };
+        }
+        Some(
+            std::str::from_utf8(&buffer[..width])
+                .map(|s| s.chars().next().unwrap())
+                .map_err(|_| invalid()),
+        )
     }
-    panic!("No unique char sequence of lenght {} found", length);
 }
 
-fn main() {
-    let input = input::get_input(6);
-    println!("Part 1: {}", get_position_of_n_unique_chars(&input, 4));
-    println!("Part 2: {}", get_position_of_n_unique_chars(&input, 14));
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_char()? {
+                Ok(c) if self.detector.push(c) => return Some(Ok(self.detector.position())),
+                Ok(_) => (),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Number of characters processed until the last `length` characters are all different.
+pub fn get_position_of_n_unique_chars(inp: &str, length: usize) -> usize {
+    marker_positions(inp, length)
+        .next()
+        .unwrap_or_else(|| panic!("No unique char sequence of length {} found", length))
+}
+
+/// End of the first start-of-packet (4) and start-of-message (14) marker.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        // The trailing line break is not part of the datastream
+        Ok(input.trim_end())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        get_position_of_n_unique_chars(input, 4)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        get_position_of_n_unique_chars(input, 14)
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_rng::Lcg;
     const INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
 
     #[test]
@@ -40,4 +171,71 @@ mod tests {
     fn test_part_2() {
         assert_eq!(get_position_of_n_unique_chars(INPUT, 14), 19);   
     }
+
+    #[test]
+    fn test_all_markers() {
+        assert_eq!(marker_positions("abcabd", 3).collect::<Vec<usize>>(), [3, 4, 5, 6]);
+        assert_eq!(marker_positions("aabbab", 2).collect::<Vec<usize>>(), [3, 5, 6]);
+        assert_eq!(marker_positions("aaaa", 2).count(), 0);
+        // A marker ending at the last character is found
+        assert_eq!(get_position_of_n_unique_chars("aaab", 2), 4);
+    }
+
+    #[test]
+    fn test_alphabets() {
+        assert_eq!(marker_positions("ääöüß", 3).collect::<Vec<usize>>(), [4, 5]);
+        assert_eq!(marker_positions("🎄🎄⭐🎁", 3).collect::<Vec<usize>>(), [4]);
+
+        let mut detector = MarkerDetector::new(3).unwrap();
+        let found: Vec<bool> = [1, 2, 1, 3, 4].into_iter().map(|n| detector.push(n)).collect();
+        assert_eq!(found, [false, false, false, true, true]);
+        assert!(MarkerDetector::<u8>::new(0).is_none());
+    }
+
+    #[test]
+    fn test_stream() {
+        let markers = |input: &[u8]| Markers::new(input, 4).collect::<io::Result<Vec<usize>>>();
+        let expected: Vec<usize> = marker_positions(INPUT, 4).collect();
+        assert_eq!(markers(INPUT.as_bytes()).unwrap(), expected);
+        assert_eq!(markers("äöü€🎄".as_bytes()).unwrap(), [4, 5]);
+
+        // Truncated and invalid characters
+        let invalid = |input: &[u8]| markers(input).unwrap_err().kind();
+        assert_eq!(invalid(&"ab€".as_bytes()[..4]), io::ErrorKind::InvalidData);
+        assert_eq!(invalid(b"abc\xffd"), io::ErrorKind::InvalidData);
+    }
+
+    // The previous implementation, sorting every window
+    fn naive_position(inp: &str, length: usize) -> Option<usize> {
+        fn has_unique_chars(word: &str) -> bool {
+            let mut chars = word.chars().collect::<Vec<char>>();
+            chars.sort();
+            chars.dedup();
+            chars.len() == word.len()
+        }
+
+        (0..(inp.len() - length))
+            .find(|&i| has_unique_chars(&inp[i..i + length]))
+            .map(|i| i + length)
+    }
+
+    #[test]
+    fn test_agrees_with_naive() {
+        let mut rng = Lcg::new(1);
+
+        for _ in 0..200 {
+            let letters = 2 + rng.below(25);
+            let input: String = (0..20 + rng.below(200))
+                .map(|_| (b'a' + rng.below(letters) as u8) as char)
+                .collect();
+            for length in [1, 4, 14] {
+                let naive = naive_position(&input, length);
+                let first = marker_positions(&input, length).next();
+                // The old range stopped before a marker ending at the last character
+                if naive.is_some() || first != Some(input.len()) {
+                    assert_eq!(first, naive, "{} {}", input, length);
+                }
+            }
+        }
+    }
 }
@@ -1,9 +1,12 @@
+//! Day 7: No Space Left On Device
+
 use core::panic;
 use std::collections::HashMap;
 
-mod input;
+use crate::Solution;
 
-fn get_folders_with_sizes(input: &str) -> HashMap<String, usize> {
+/// Total size of every directory in the terminal output, keyed by its concatenated path.
+pub fn get_folders_with_sizes(input: &str) -> HashMap<String, usize> {
     let mut folder_sizes = HashMap::new();
     let mut current_path = Vec::new();
 
@@ -42,7 +45,7 @@ fn get_folders_with_sizes(input: &str) -> HashMap<String, usize> {
 
 fn part_1(input: &str) -> usize {
     let folders = get_folders_with_sizes(input);
-    let small_folders = folders.iter().filter_map(|(_k, v)| {
+    let small_folders = folders.values().filter_map(|v| {
         if *v <= 100000 {
             Some(*v)
         } else {
@@ -55,16 +58,31 @@ fn part_1(input: &str) -> usize {
 fn part_2(input: &str) -> usize {
     let folders = get_folders_with_sizes(input);
     let needed_space = 30000000 - (70000000 - folders.get("/").unwrap());
-    let mut sizes = folders.iter().map(|(_k, v)| *v).collect::<Vec<usize>>();
+    let mut sizes = folders.values().copied().collect::<Vec<usize>>();
     sizes.sort();
     *sizes.iter().find(|&&v| v >= needed_space).unwrap()
 
 }
 
-fn main() {
-    let input = input::get_input(7);
-    println!("Part 1: {}", part_1(&input));
-    println!("Part 2: {}", part_2(&input));
+/// Sum of all directories of at most 100000, and the smallest directory that frees enough space.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        part_1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
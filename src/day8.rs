@@ -1,13 +1,30 @@
-mod input;
+//! Day 8: Treetop Tree House
 
-fn main() {
-    let inp = input::get_input(8);
-    let parsed_input = parse_input(&inp);
-    println!("Part 1: {}", part_1(&parsed_input));
-    println!("Part 2: {}", part_2(&parsed_input));
+use crate::Solution;
+
+/// Number of trees visible from outside the grid, and the highest scenic score.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = Vec<Vec<u8>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        part_1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        part_2(input)
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
+/// Parses the tree heights into a square grid.
+pub fn parse_input(input: &str) -> Vec<Vec<u8>> {
     input
         .lines()
         .map(|line| {
@@ -33,7 +50,7 @@ fn get_coordinates_by_direction(
     }
 }
 
-fn get_visible_trees(inp: &Vec<Vec<u8>>, visibility_map: &mut [Vec<u8>], direction: &str) {
+fn get_visible_trees(inp: &[Vec<u8>], visibility_map: &mut [Vec<u8>], direction: &str) {
     let size = inp.len();
 
     for y in 1..size - 1 {
@@ -49,7 +66,7 @@ fn get_visible_trees(inp: &Vec<Vec<u8>>, visibility_map: &mut [Vec<u8>], directi
     }
 }
 
-fn part_1(inp: &Vec<Vec<u8>>) -> usize {
+fn part_1(inp: &[Vec<u8>]) -> usize {
     let mut visibility_map = vec![vec![0; inp.len()]; inp.len()];
 
     get_visible_trees(inp, &mut visibility_map, "right");
@@ -60,7 +77,8 @@ fn part_1(inp: &Vec<Vec<u8>>) -> usize {
     visibility_map.iter().flatten().filter(|&&v| v == 1).count() + 4 * inp.len() - 4
 }
 
-fn get_scenic_score(i: usize, j: usize, inp: &Vec<Vec<u8>>) -> usize {
+/// Product of the viewing distances in all four directions from the tree at `(i, j)`.
+pub fn get_scenic_score(i: usize, j: usize, inp: &[Vec<u8>]) -> usize {
     let mut viewing_distance = 0;
     if j < inp.len() - 1 {
         for k in (j + 1)..inp.len() {
@@ -108,7 +126,7 @@ fn get_scenic_score(i: usize, j: usize, inp: &Vec<Vec<u8>>) -> usize {
     scenic_score * viewing_distance
 }
 
-fn part_2(inp: &Vec<Vec<u8>>) -> usize {
+fn part_2(inp: &[Vec<u8>]) -> usize {
     let mut distances: Vec<usize> = Vec::new();
     for i in 0..inp.len() {
         for j in 0..inp.len() {
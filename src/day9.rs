@@ -1,19 +1,25 @@
+//! Day 9: Rope Bridge
+
 use std::{
     collections::HashMap,
     fmt::{Display, Error, Formatter},
 };
 
-mod input;
+use crate::Solution;
 
-type Position = [i32; 2];
+/// `[x, y]` position of a knot.
+pub type Position = [i32; 2];
 
-struct Rope {
-    knots: Vec<Position>,
-    visited_positions: HashMap<Position, bool>,
+/// A rope of knots where every knot follows the one in front of it.
+pub struct Rope {
+    pub knots: Vec<Position>,
+    /// Every position the tail has visited.
+    pub visited_positions: HashMap<Position, bool>,
 }
 
 impl Rope {
-    fn with_lenght(length: usize) -> Self {
+    /// A rope of `length` knots, all starting at the origin.
+    pub fn with_lenght(length: usize) -> Self {
         Rope {
             knots: vec![[0, 0]; length],
             visited_positions: HashMap::new(),
@@ -57,7 +63,8 @@ impl Rope {
         }
     }
 
-    fn move_direction(&mut self, direction: &str) {
+    /// Moves the head one step in the direction `U`, `D`, `L` or `R` and lets the other knots follow.
+    pub fn move_direction(&mut self, direction: &str) {
         match direction {
             "U" => {
                 self.move_up();
@@ -128,10 +135,25 @@ impl Display for Rope {
     }
 }
 
-fn main() {
-    let inp = input::get_input(9);
-    println!("Part 1: {}", part_1(&inp));
-    println!("Part 2: {}", part_2(&inp));
+/// Number of positions visited by the tail of a rope with 2 and with 10 knots.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        part_1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        part_2(input)
+    }
 }
 
 fn part_1(input: &str) -> usize {
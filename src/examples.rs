@@ -0,0 +1,136 @@
+//! Example inputs taken from the puzzle descriptions, stored in `test_input/dayN.txt`. Paths are
+//! relative to the root of the crate, where `aoc examples` is run and tests are run by cargo.
+
+#[cfg(test)]
+use std::{fs, io};
+use std::path::{Path, PathBuf};
+
+pub const EXAMPLES_DIR: &str = "test_input";
+
+/// Path of the saved example input of a day.
+pub fn example_path(day: u8) -> PathBuf {
+    Path::new(EXAMPLES_DIR).join(format!("day{}.txt", day))
+}
+
+/// Loads the saved example input of a day in the tests of the days.
+#[cfg(test)]
+pub(crate) fn load_example(day: u8) -> io::Result<String> {
+    fs::read_to_string(example_path(day))
+}
+
+/// Extracts the text of every `<pre><code>` block of a puzzle page, in page order.
+///
+/// Tags inside the blocks (like the `<em>` highlights) are stripped and HTML entities decoded.
+pub fn extract_examples(html: &str) -> Vec<String> {
+    let mut examples = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+        let end = match rest.find("</code></pre>") {
+            Some(end) => end,
+            None => break,
+        };
+        examples.push(decode_entities(&strip_tags(&rest[..end])));
+        rest = &rest[end..];
+    }
+
+    examples
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // Entities are short, an `&` without a `;` soon after is taken literally
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 8)
+            .map(|end| &rest[1..end + 1]);
+        match entity.and_then(decode_entity) {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[entity.unwrap().len() + 2..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => entity.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day5, Solution};
+
+    const DAY5_PAGE: &str = include_str!("../test_input/fixtures/day5.html");
+
+    #[test]
+    fn test_extract_examples() {
+        let examples = extract_examples(DAY5_PAGE);
+        assert_eq!(examples.len(), 2);
+        assert!(examples[0].starts_with("    [D]    \n[N] [C]    \n"));
+        assert!(examples[0].ends_with("move 1 from 1 to 2\n"));
+        assert_eq!(examples[1], "        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3\n");
+
+        let input = day5::Day5::parse(&examples[0]).unwrap();
+        assert_eq!(day5::Day5::part1(&input), "CMZ");
+        assert_eq!(day5::Day5::part2(&input), "MCD");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        let html = "<pre><code>$ cd /\n&lt;dir&gt; <em>a &amp; b</em>\n&#65;&#x42; &unknown; & x</code></pre>";
+        assert_eq!(
+            extract_examples(html),
+            vec!["$ cd /\n<dir> a & b\nAB &unknown; & x".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_load_example() {
+        assert!(load_example(10).unwrap().starts_with("addx 15\n"));
+        assert!(load_example(99).is_err());
+    }
+}
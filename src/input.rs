@@ -1,23 +1,719 @@
+//! Fetching and caching of puzzle inputs.
+
+use crate::unlock::{self, Clock, SystemClock};
 use dotenv::dotenv;
-use reqwest::blocking::Client;
-use std::env;
-
-pub fn get_input(day: u8) -> String {
-    dotenv().ok();
-
-    let client = Client::new();
-    
-    client
-        .get(format!("https://adventofcode.com/2022/day/{}/input", day))
-        .header(
-            "Cookie",
-            format!(
-                "session={}",
-                env::var("AOC_SESSION").expect("Could not find session environment variable")
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    header::{RETRY_AFTER, USER_AGENT},
+    StatusCode,
+};
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_YEAR: u16 = 2022;
+const DEFAULT_CACHE_DIR: &str = "inputs";
+const DEFAULT_USER_AGENT: &str = concat!("advent22/", env!("CARGO_PKG_VERSION"));
+const UNLOCK_RETRIES: u32 = 5;
+
+/// Errors while reading or downloading a puzzle input.
+#[derive(Debug)]
+pub enum InputError {
+    MissingSession,
+    Network(reqwest::Error),
+    Status(StatusCode),
+    SessionExpired,
+    NotUnlocked(u8),
+    Io(io::Error),
+    UnexpectedResponse(String),
+    InvalidConfig(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::MissingSession => write!(
+                f,
+                "AOC_SESSION is not set, add your session cookie to the environment or a .env file"
+            ),
+            InputError::Network(e) => write!(f, "Could not reach the Advent of Code server: {}", e),
+            InputError::Status(status) => write!(f, "Unexpected response status: {}", status),
+            InputError::SessionExpired => write!(
+                f,
+                "The session cookie was rejected, log in again and update AOC_SESSION"
             ),
-        )
-        .send()
-        .expect("Error getting input")
-        .text()
-        .expect("Error parsing input")
+            InputError::NotUnlocked(day) => write!(f, "Day {} has not been unlocked yet", day),
+            InputError::Io(e) => write!(f, "Could not read or write input: {}", e),
+            InputError::UnexpectedResponse(text) => write!(f, "Unexpected response: {}", text),
+            InputError::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<reqwest::Error> for InputError {
+    fn from(e: reqwest::Error) -> Self {
+        InputError::Network(e)
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(e: io::Error) -> Self {
+        InputError::Io(e)
+    }
+}
+
+/// Where and for which event inputs are fetched and answers are submitted.
+///
+/// `Config::from_env` reads the settings from the environment or a `.env` file:
+/// `AOC_SESSION`, `AOC_BASE_URL`, `AOC_YEAR`, `AOC_CACHE_DIR`, `AOC_REFRESH` and
+/// `AOC_USER_AGENT`.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u16,
+    pub cache_dir: PathBuf,
+    /// Download the input even if it is already cached.
+    pub refresh: bool,
+    /// Sent with every request, it should say who to contact about the traffic.
+    pub user_agent: String,
+    /// Minimum time between two requests to the same server.
+    pub min_interval: Duration,
+    /// Timeout of a single request.
+    pub timeout: Duration,
+    /// How often a request is repeated after a transient failure.
+    pub retries: u32,
+    /// Wait before the first retry, doubled for every further one.
+    pub backoff: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            year: DEFAULT_YEAR,
+            cache_dir: PathBuf::from(DEFAULT_CACHE_DIR),
+            refresh: false,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            min_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(30),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_env() -> Result<Config, InputError> {
+        dotenv().ok();
+
+        let mut config = Config {
+            session: env::var("AOC_SESSION").ok(),
+            refresh: env::var("AOC_REFRESH").is_ok(),
+            ..Config::default()
+        };
+
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        if let Ok(year) = env::var("AOC_YEAR") {
+            config.year = year
+                .parse()
+                .map_err(|_| InputError::InvalidConfig(format!("AOC_YEAR is not a year: {}", year)))?;
+        }
+        if let Ok(cache_dir) = env::var("AOC_CACHE_DIR") {
+            config.cache_dir = PathBuf::from(cache_dir);
+        }
+        if let Ok(user_agent) = env::var("AOC_USER_AGENT") {
+            config.user_agent = user_agent;
+        }
+
+        Ok(config)
+    }
+
+    fn day_url(&self, day: u8, path: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, self.year, day, path)
+    }
+
+    /// Path of the cached input of a day.
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(self.year.to_string())
+            .join(format!("day{}.txt", day))
+    }
+
+    pub(crate) fn session(&self) -> Result<&str, InputError> {
+        self.session.as_deref().ok_or(InputError::MissingSession)
+    }
+}
+
+// One client for all requests, so connections are reused
+fn client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(Client::new)
+}
+
+// Waits until `min_interval` has passed since the last request to the server of the configuration.
+// The lock is held while waiting, so concurrent requests are spaced out as well.
+fn throttle(config: &Config) {
+    static LAST_REQUESTS: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+    let mut last_requests = LAST_REQUESTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    if let Some(last) = last_requests.get(&config.base_url) {
+        let next = *last + config.min_interval;
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        }
+    }
+    last_requests.insert(config.base_url.clone(), Instant::now());
+}
+
+// Sends a request built by `build`, returning the status and the body of the response.
+//
+// Connection failures and the statuses of an overloaded server are retried with exponential
+// backoff, or after the delay of a `Retry-After` header. Timeouts and gateway errors are only
+// retried for `idempotent` requests, the server may have handled the first one already.
+pub(crate) fn send(
+    config: &Config,
+    idempotent: bool,
+    build: impl Fn(&Client) -> RequestBuilder,
+) -> Result<(StatusCode, String), InputError> {
+    // Building the client the first time is slow, it must not delay a request after throttling
+    let client = client();
+    let mut attempt = 0;
+    loop {
+        throttle(config);
+        let result = build(client)
+            .header(USER_AGENT, &config.user_agent)
+            .timeout(config.timeout)
+            .send()
+            .and_then(|response| {
+                let retry_after = retry_after(&response);
+                Ok((response.status(), retry_after, response.text()?))
+            });
+
+        let transient = match &result {
+            // The server refused these without handling the request
+            Ok((status, _, _))
+                if *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::SERVICE_UNAVAILABLE =>
+            {
+                true
+            }
+            Ok((status, _, _)) => {
+                let gateway_error =
+                    *status == StatusCode::BAD_GATEWAY || *status == StatusCode::GATEWAY_TIMEOUT;
+                idempotent && gateway_error
+            }
+            Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+        };
+        if !transient || attempt >= config.retries {
+            let (status, _, body) = result?;
+            return Ok((status, body));
+        }
+
+        let delay = match &result {
+            Ok((_, Some(retry_after), _)) => *retry_after,
+            _ => config.backoff * 2u32.pow(attempt),
+        };
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+// Delay of a `Retry-After` header given in seconds, HTTP dates are not supported
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Network,
+    Stdin,
+    File(PathBuf),
+}
+
+/// Reads the input of a day from the given source, downloading it for `InputSource::Network`.
+pub fn read_input(day: u8, source: InputSource) -> Result<String, InputError> {
+    match source {
+        InputSource::Network => get_input(day),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        InputSource::File(path) => Ok(fs::read_to_string(path)?),
+    }
+}
+
+/// Downloads the input of a day with the configuration from the environment, see `get_input_with`.
+pub fn get_input(day: u8) -> Result<String, InputError> {
+    get_input_with(&Config::from_env()?, day)
+}
+
+/// Downloads the input of a day, using the session cookie of the configuration.
+///
+/// Inputs are cached in `<cache_dir>/<year>/dayN.txt`, by default `inputs/2022/dayN.txt`.
+/// They are only downloaded again if `refresh` is set.
+pub fn get_input_with(config: &Config, day: u8) -> Result<String, InputError> {
+    let path = config.cache_path(day);
+
+    if !config.refresh {
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+    }
+
+    let input = download_input(config, day)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, &input)?;
+
+    Ok(input)
+}
+
+/// Waits until the day is unlocked and downloads its input with the configuration from the
+/// environment, see `get_input_when_unlocked_with`.
+pub fn get_input_when_unlocked(
+    day: u8,
+    on_tick: impl FnMut(Duration),
+) -> Result<String, InputError> {
+    get_input_when_unlocked_with(&Config::from_env()?, &SystemClock, day, on_tick)
+}
+
+/// Sleeps until the day unlocks, calling `on_tick` with the time left, then downloads and
+/// caches its input like `get_input_with`.
+///
+/// The server clock may be slightly behind, a `NotUnlocked` response right after the unlock
+/// time is retried a few times.
+pub fn get_input_when_unlocked_with(
+    config: &Config,
+    clock: &impl Clock,
+    day: u8,
+    on_tick: impl FnMut(Duration),
+) -> Result<String, InputError> {
+    unlock::wait_for_unlock(clock, config.year, day, on_tick);
+
+    let mut attempt = 0;
+    loop {
+        match get_input_with(config, day) {
+            Err(InputError::NotUnlocked(_)) if attempt < UNLOCK_RETRIES => {
+                clock.sleep(Duration::from_secs(1));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn download_input(config: &Config, day: u8) -> Result<String, InputError> {
+    let session = config.session()?;
+    let (status, body) = send(config, true, |client| {
+        client
+            .get(config.day_url(day, "/input"))
+            .header("Cookie", format!("session={}", session))
+    })?;
+
+    match status {
+        StatusCode::OK => Ok(body),
+        // "Please log in to get your puzzle input."
+        StatusCode::BAD_REQUEST | StatusCode::INTERNAL_SERVER_ERROR if body.contains("log in") => {
+            Err(InputError::SessionExpired)
+        }
+        // "Please don't repeatedly request this endpoint before it unlocks!"
+        StatusCode::NOT_FOUND if body.contains("before it unlocks") => {
+            Err(InputError::NotUnlocked(day))
+        }
+        status => Err(InputError::Status(status)),
+    }
+}
+
+/// Downloads the puzzle page of a day with the configuration from the environment.
+pub fn get_puzzle_page(day: u8) -> Result<String, InputError> {
+    get_puzzle_page_with(&Config::from_env()?, day)
+}
+
+/// Downloads the HTML puzzle page of a day. The session cookie is optional here, without it
+/// the page only contains the description of the first part.
+pub fn get_puzzle_page_with(config: &Config, day: u8) -> Result<String, InputError> {
+    let (status, body) = send(config, true, |client| {
+        let request = client.get(config.day_url(day, ""));
+        match &config.session {
+            Some(session) => request.header("Cookie", format!("session={}", session)),
+            None => request,
+        }
+    })?;
+
+    match status {
+        StatusCode::OK => Ok(body),
+        StatusCode::NOT_FOUND => Err(InputError::NotUnlocked(day)),
+        status => Err(InputError::Status(status)),
+    }
+}
+
+/// Outcome of submitting an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubmitResult {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Another answer was submitted too recently, try again after the given time.
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl fmt::Display for SubmitResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitResult::Correct => write!(f, "That's the right answer!"),
+            SubmitResult::TooHigh => write!(f, "That's not the right answer, it is too high"),
+            SubmitResult::TooLow => write!(f, "That's not the right answer, it is too low"),
+            SubmitResult::Incorrect => write!(f, "That's not the right answer"),
+            SubmitResult::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again",
+                wait.as_secs()
+            ),
+            SubmitResult::AlreadySolved => write!(f, "This part is already solved"),
+        }
+    }
+}
+
+/// Submits an answer with the configuration from the environment, see `submit_answer_with`.
+pub fn submit_answer(day: u8, part: u8, answer: &str) -> Result<SubmitResult, InputError> {
+    submit_answer_with(&Config::from_env()?, day, part, answer)
+}
+
+/// Submits the answer for a part of a day, using the session cookie of the configuration.
+pub fn submit_answer_with(
+    config: &Config,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<SubmitResult, InputError> {
+    let session = config.session()?;
+    let (status, body) = send(config, false, |client| {
+        client
+            .post(config.day_url(day, "/answer"))
+            .header("Cookie", format!("session={}", session))
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+    })?;
+
+    match status {
+        StatusCode::OK => parse_submit_response(&body),
+        StatusCode::BAD_REQUEST | StatusCode::INTERNAL_SERVER_ERROR if body.contains("log in") => {
+            Err(InputError::SessionExpired)
+        }
+        StatusCode::NOT_FOUND => Err(InputError::NotUnlocked(day)),
+        status => Err(InputError::Status(status)),
+    }
+}
+
+fn parse_submit_response(html: &str) -> Result<SubmitResult, InputError> {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Ok(SubmitResult::Correct)
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Ok(SubmitResult::TooHigh)
+        } else if text.contains("too low") {
+            Ok(SubmitResult::TooLow)
+        } else {
+            Ok(SubmitResult::Incorrect)
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Ok(SubmitResult::RateLimited(parse_wait_time(&text)))
+    } else if text.contains("Did you already complete it?") {
+        Ok(SubmitResult::AlreadySolved)
+    } else {
+        Err(InputError::UnexpectedResponse(text))
+    }
+}
+
+// The message of the response is inside the `<article>` element, tags are stripped
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Parses "You have 1m 23s left to wait." into the remaining time
+fn parse_wait_time(text: &str) -> Duration {
+    let wait = text
+        .split("You have ")
+        .nth(1)
+        .and_then(|rest| rest.split(" left to wait").next())
+        .unwrap_or("");
+
+    let seconds = wait.split_whitespace().fold(0, |acc, part| {
+        let (number, unit) = part.split_at(part.len().saturating_sub(1));
+        let number = number.parse::<u64>().unwrap_or(0);
+        match unit {
+            "h" => acc + number * 3600,
+            "m" => acc + number * 60,
+            "s" => acc + number,
+            _ => acc,
+        }
+    });
+
+    Duration::from_secs(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_server::TestServer, unlock::tests::FakeClock};
+
+    const CORRECT: &str = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit. <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a></p></article>\n</main>";
+    const TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const TOO_LOW: &str = "<main>\n<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const RATE_LIMITED: &str = "<main>\n<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const ALREADY_SOLVED: &str = "<main>\n<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+
+    #[test]
+    fn test_parse_submit_response() {
+        assert_eq!(parse_submit_response(CORRECT).unwrap(), SubmitResult::Correct);
+        assert_eq!(parse_submit_response(TOO_HIGH).unwrap(), SubmitResult::TooHigh);
+        assert_eq!(parse_submit_response(TOO_LOW).unwrap(), SubmitResult::TooLow);
+        assert_eq!(
+            parse_submit_response(RATE_LIMITED).unwrap(),
+            SubmitResult::RateLimited(Duration::from_secs(83))
+        );
+        assert_eq!(
+            parse_submit_response(ALREADY_SOLVED).unwrap(),
+            SubmitResult::AlreadySolved
+        );
+        assert!(parse_submit_response("<article><p>Something else</p></article>").is_err());
+    }
+
+    fn test_config(server: &TestServer) -> Config {
+        Config {
+            session: Some("test-session".to_string()),
+            base_url: server.url.clone(),
+            cache_dir: env::temp_dir().join(format!("advent22-test-{}", server.port())),
+            min_interval: Duration::ZERO,
+            backoff: Duration::from_millis(10),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_get_input_caches_download() {
+        let server = TestServer::start(vec![(200, "1000\n2000\n".to_string())]);
+        let config = test_config(&server);
+
+        assert_eq!(get_input_with(&config, 1).unwrap(), "1000\n2000\n");
+        // The server only answers once, the second call has to use the cache
+        assert_eq!(get_input_with(&config, 1).unwrap(), "1000\n2000\n");
+        assert!(config.cache_dir.join("2022").join("day1.txt").exists());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=test-session"));
+
+        fs::remove_dir_all(&config.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_retry_with_backoff() {
+        let server = TestServer::start(vec![
+            (503, "Busy".to_string()),
+            (502, "Busy".to_string()),
+            (200, "1000\n".to_string()),
+        ]);
+        let config = Config {
+            refresh: true,
+            ..test_config(&server)
+        };
+
+        assert_eq!(get_input_with(&config, 1).unwrap(), "1000\n");
+        fs::remove_dir_all(&config.cache_dir).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].received - requests[0].received >= config.backoff);
+        assert!(requests[2].received - requests[1].received >= config.backoff * 2);
+        assert!(requests
+            .iter()
+            .all(|r| r.header("user-agent") == Some(DEFAULT_USER_AGENT)));
+    }
+
+    #[test]
+    fn test_retries_exhausted() {
+        let server = TestServer::start(vec![(503, "Busy".to_string()); 3]);
+        let config = Config {
+            retries: 2,
+            ..test_config(&server)
+        };
+
+        assert!(matches!(
+            get_puzzle_page_with(&config, 1),
+            Err(InputError::Status(StatusCode::SERVICE_UNAVAILABLE))
+        ));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_min_interval() {
+        let server = TestServer::start(vec![(200, "page".to_string()); 3]);
+        let config = Config {
+            min_interval: Duration::from_millis(100),
+            ..test_config(&server)
+        };
+
+        for _ in 0..3 {
+            get_puzzle_page_with(&config, 1).unwrap();
+        }
+
+        let requests = server.requests();
+        for pair in requests.windows(2) {
+            assert!(pair[1].received - pair[0].received >= Duration::from_millis(90));
+        }
+    }
+
+    #[test]
+    fn test_get_input_other_year() {
+        let server = TestServer::start(vec![(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.".to_string(),
+        )]);
+        let config = Config {
+            year: 2015,
+            ..test_config(&server)
+        };
+
+        assert!(matches!(
+            get_input_with(&config, 25),
+            Err(InputError::NotUnlocked(25))
+        ));
+        assert_eq!(server.requests()[0].path, "/2015/day/25/input");
+    }
+
+    #[test]
+    fn test_get_puzzle_page() {
+        let page = include_str!("../test_input/fixtures/day5.html");
+        let server = TestServer::start(vec![(200, page.to_string())]);
+
+        assert_eq!(get_puzzle_page_with(&test_config(&server), 5).unwrap(), page);
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/2022/day/5");
+        assert_eq!(requests[0].header("cookie"), Some("session=test-session"));
+    }
+
+    #[test]
+    fn test_get_input_when_unlocked() {
+        let server = TestServer::start(vec![
+            (404, "Please don't repeatedly request this endpoint before it unlocks!".to_string()),
+            (200, "1000\n".to_string()),
+        ]);
+        let config = test_config(&server);
+        let clock = FakeClock::new(unlock::unlock_time(2022, 4) - Duration::from_secs(90));
+
+        let mut ticks = 0;
+        let input = get_input_when_unlocked_with(&config, &clock, 4, |_| ticks += 1).unwrap();
+        assert_eq!(input, "1000\n");
+        assert_eq!(ticks, 90);
+        // One more second after the server still reported the day as locked
+        assert_eq!(
+            clock.now(),
+            unlock::unlock_time(2022, 4) + Duration::from_secs(1)
+        );
+        assert!(config.cache_path(4).exists());
+
+        fs::remove_dir_all(&config.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let server = TestServer::start(vec![(200, CORRECT.to_string())]);
+
+        let result = submit_answer_with(&test_config(&server), 1, 2, "12345").unwrap();
+        assert_eq!(result, SubmitResult::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/1/answer");
+        assert_eq!(requests[0].header("cookie"), Some("session=test-session"));
+        assert_eq!(requests[0].body, "level=2&answer=12345");
+    }
+
+    #[test]
+    fn test_submit_answer_not_retried() {
+        // A gateway error may come after the answer was handled, submitting again could count as
+        // a wrong answer
+        let server = TestServer::start(vec![
+            (502, "Bad gateway".to_string()),
+            (200, CORRECT.to_string()),
+        ]);
+
+        assert!(matches!(
+            submit_answer_with(&test_config(&server), 1, 1, "1"),
+            Err(InputError::Status(StatusCode::BAD_GATEWAY))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_submit_answer_retry_after() {
+        let retry_after = vec![("Retry-After".to_string(), "1".to_string())];
+        let server = TestServer::start_with_headers(vec![
+            (429, retry_after, "Slow down".to_string()),
+            (200, Vec::new(), CORRECT.to_string()),
+        ]);
+
+        let result = submit_answer_with(&test_config(&server), 1, 1, "1").unwrap();
+        assert_eq!(result, SubmitResult::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].received - requests[0].received >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_submit_answer_expired_session() {
+        let server = TestServer::start(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string(),
+        )]);
+
+        assert!(matches!(
+            submit_answer_with(&test_config(&server), 1, 1, "1"),
+            Err(InputError::SessionExpired)
+        ));
+    }
 }
@@ -0,0 +1,297 @@
+//! Inclusive integer intervals and sets of them, used for the section assignments of day 4 and
+//! the sensor coverage of day 15.
+
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+/// An interval `start..=end` of integers, never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Section {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Section {
+    /// The interval `start..=end`, `None` if it would be empty.
+    pub fn new(start: i64, end: i64) -> Option<Section> {
+        (start <= end).then_some(Section { start, end })
+    }
+
+    /// Number of integers in the interval.
+    #[allow(clippy::len_without_is_empty)] // Sections are never empty
+    pub fn len(&self) -> u64 {
+        self.end.abs_diff(self.start) + 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether `other` lies completely inside `self`.
+    pub fn contains_section(&self, other: &Section) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the intervals share at least one integer.
+    pub fn overlaps(&self, other: &Section) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Section) -> Option<Section> {
+        Section::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Both intervals as one, if they overlap or are adjacent.
+    pub fn union(&self, other: &Section) -> Option<Section> {
+        let touching = self.start <= other.end.saturating_add(1)
+            && other.start <= self.end.saturating_add(1);
+        touching.then(|| Section {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// The parts of `self` that are not in `other`, at most two.
+    pub fn difference(&self, other: &Section) -> Vec<Section> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        [
+            Section::new(self.start, other.start.saturating_sub(1)),
+            Section::new(other.end.saturating_add(1), self.end),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Errors for a section written as `start-end`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseSectionError {
+    MissingDash(String),
+    InvalidNumber(ParseIntError),
+    /// The end lies before the start.
+    Empty(String),
+}
+
+impl fmt::Display for ParseSectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSectionError::MissingDash(s) => write!(f, "Expected start-end, got {:?}", s),
+            ParseSectionError::InvalidNumber(e) => write!(f, "Invalid section bound: {}", e),
+            ParseSectionError::Empty(s) => write!(f, "Section {} ends before it starts", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseSectionError {}
+
+impl From<ParseIntError> for ParseSectionError {
+    fn from(e: ParseIntError) -> Self {
+        ParseSectionError::InvalidNumber(e)
+    }
+}
+
+impl FromStr for Section {
+    type Err = ParseSectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The start may be negative, the dash is searched after its first character
+        let dash = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-')
+            .map(|(i, _)| i)
+            .ok_or_else(|| ParseSectionError::MissingDash(s.to_string()))?;
+        let start = s[..dash].trim().parse()?;
+        let end = s[dash + 1..].trim().parse()?;
+        Section::new(start, end).ok_or_else(|| ParseSectionError::Empty(s.to_string()))
+    }
+}
+
+/// A union of sections, kept as sorted, disjoint and non-adjacent sections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    sections: Vec<Section>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    /// Adds a section, merging it with the sections it overlaps or touches.
+    pub fn insert(&mut self, section: Section) {
+        // Sections before the first one that can touch `section` stay as they are
+        let first = self
+            .sections
+            .partition_point(|s| s.end.saturating_add(1) < section.start);
+        let mut merged = section;
+        let mut last = first;
+        while let Some(union) = self.sections.get(last).and_then(|s| s.union(&merged)) {
+            merged = union;
+            last += 1;
+        }
+        self.sections.splice(first..last, [merged]);
+    }
+
+    /// The merged sections in ascending order.
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.sections.iter().map(Section::len).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.sections.partition_point(|s| s.end < value);
+        self.sections.get(index).is_some_and(|s| s.contains(value))
+    }
+
+    /// The parts of `within` that are not in the set.
+    pub fn gaps(&self, within: &Section) -> Vec<Section> {
+        self.sections
+            .iter()
+            .fold(vec![*within], |gaps, section| {
+                gaps.iter().flat_map(|gap| gap.difference(section)).collect()
+            })
+    }
+
+    /// The integers that lie in more than `k` of the sections, counting duplicates.
+    pub fn covered_more_than(sections: &[Section], k: usize) -> IntervalSet {
+        // Sweep over the bounds, +1 where a section starts and -1 after it ends
+        let mut events: Vec<(i64, i64)> = sections
+            .iter()
+            .flat_map(|s| [(s.start, 1), (s.end.saturating_add(1), -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut set = IntervalSet::new();
+        let mut depth = 0;
+        let mut covered_since = None;
+        for (position, change) in events {
+            depth += change;
+            match covered_since {
+                None if depth > k as i64 => covered_since = Some(position),
+                Some(start) if depth <= k as i64 => {
+                    set.extend(Section::new(start, position - 1));
+                    covered_since = None;
+                }
+                _ => (),
+            }
+        }
+        set
+    }
+}
+
+impl Extend<Section> for IntervalSet {
+    fn extend<I: IntoIterator<Item = Section>>(&mut self, sections: I) {
+        for section in sections {
+            self.insert(section);
+        }
+    }
+}
+
+impl FromIterator<Section> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Section>>(sections: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(sections);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(start: i64, end: i64) -> Section {
+        Section::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn test_section() {
+        let a = section(2, 8);
+        let b = section(3, 7);
+        let c = section(7, 9);
+
+        assert!(a.contains_section(&b) && !b.contains_section(&a));
+        assert!(a.overlaps(&c) && !b.overlaps(&section(8, 8)));
+        assert_eq!(a.intersection(&c), Some(section(7, 8)));
+        assert_eq!(b.intersection(&section(8, 9)), None);
+        assert_eq!(b.union(&section(8, 9)), Some(section(3, 9)));
+        assert_eq!(b.union(&section(9, 9)), None);
+        assert_eq!(a.difference(&b), [section(2, 2), section(8, 8)]);
+        assert_eq!(a.difference(&section(0, 5)), [section(6, 8)]);
+        assert_eq!(b.difference(&a), []);
+        assert_eq!(a.len(), 7);
+        assert_eq!(Section::new(3, 2), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2-4".parse(), Ok(section(2, 4)));
+        assert_eq!("-5--2".parse(), Ok(section(-5, -2)));
+        assert_eq!(section(-5, 3).to_string(), "-5-3");
+        assert!(matches!(
+            "24".parse::<Section>(),
+            Err(ParseSectionError::MissingDash(_))
+        ));
+        assert!(matches!(
+            "2-x".parse::<Section>(),
+            Err(ParseSectionError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            "4-2".parse::<Section>(),
+            Err(ParseSectionError::Empty(_))
+        ));
+    }
+
+    #[test]
+    fn test_interval_set() {
+        // Overlapping and adjacent sections are merged
+        let set: IntervalSet = [(5, 7), (1, 2), (10, 12), (3, 4), (6, 11)]
+            .into_iter()
+            .map(|(start, end)| section(start, end))
+            .collect();
+        assert_eq!(set.sections(), [section(1, 12)]);
+
+        let set: IntervalSet = [section(1, 2), section(8, 9), section(4, 5)].into_iter().collect();
+        assert_eq!(set.sections(), [section(1, 2), section(4, 5), section(8, 9)]);
+        assert_eq!(set.len(), 6);
+        assert!(set.contains(4) && !set.contains(3) && !set.contains(10));
+        assert_eq!(
+            set.gaps(&section(0, 10)),
+            [section(0, 0), section(3, 3), section(6, 7), section(10, 10)]
+        );
+    }
+
+    #[test]
+    fn test_covered_more_than() {
+        let sections = [section(2, 4), section(6, 8), section(3, 7), section(4, 4)];
+        assert_eq!(
+            IntervalSet::covered_more_than(&sections, 0).sections(),
+            [section(2, 8)]
+        );
+        assert_eq!(
+            IntervalSet::covered_more_than(&sections, 1).sections(),
+            [section(3, 4), section(6, 7)]
+        );
+        assert_eq!(
+            IntervalSet::covered_more_than(&sections, 2).sections(),
+            [section(4, 4)]
+        );
+        assert!(IntervalSet::covered_more_than(&sections, 3).is_empty());
+    }
+}
@@ -0,0 +1,307 @@
+//! Private leaderboards, fetched from `/<year>/leaderboard/private/view/<id>.json`.
+
+use crate::{
+    input::{self, Config, InputError},
+    unlock,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    time::{Duration, UNIX_EPOCH},
+};
+
+/// A private leaderboard as returned by the JSON API.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users have no name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub global_score: u32,
+    pub last_star_ts: u64,
+    pub completion_day_level: BTreeMap<u8, DayCompletion>,
+}
+
+/// The stars of a member for one day, the second one is only there once it is solved.
+#[derive(Debug, Deserialize)]
+pub struct DayCompletion {
+    #[serde(rename = "1")]
+    pub part1: Star,
+    #[serde(rename = "2")]
+    pub part2: Option<Star>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of the solve.
+    pub get_star_ts: u64,
+    pub star_index: u64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Leaderboard, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Year of the event, falls back to the default year for unexpected values.
+    pub fn year(&self) -> u16 {
+        self.event.parse().unwrap_or(Config::default().year)
+    }
+
+    /// Members ordered by local score, then by the time of their last star.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+
+    /// Renders the ranking with a star per day, `*` for both parts and `.` for the first one.
+    pub fn render(&self) -> String {
+        let ranking = self.ranking();
+        let name_width = name_width(&ranking);
+
+        let mut table = String::new();
+        let days: String = (1..=25).map(|day| char::from(b'0' + day % 10)).collect();
+        writeln!(table, "Rank | {:<name_width$} | Score | Stars | {}", "Name", days).unwrap();
+        writeln!(
+            table,
+            "-----+-{}-+-------+-------+-{}",
+            "-".repeat(name_width),
+            "-".repeat(25)
+        )
+        .unwrap();
+
+        for (rank, member) in ranking.iter().enumerate() {
+            let stars: String = (1..=25)
+                .map(|day| match member.completion_day_level.get(&day) {
+                    Some(DayCompletion { part2: Some(_), .. }) => '*',
+                    Some(_) => '.',
+                    None => ' ',
+                })
+                .collect();
+            writeln!(
+                table,
+                "{:>4} | {:<name_width$} | {:>5} | {:>5} | {}",
+                rank + 1,
+                member.display_name(),
+                member.local_score,
+                member.stars,
+                stars
+            )
+            .unwrap();
+        }
+
+        trim_lines(&table)
+    }
+
+    /// Renders the solve times of a day since its unlock, fastest second star first.
+    pub fn render_day(&self, day: u8) -> String {
+        let year = self.year();
+        let mut members: Vec<&Member> = self
+            .members
+            .values()
+            .filter(|m| m.completion_day_level.contains_key(&day))
+            .collect();
+        members.sort_by_key(|m| {
+            let completion = &m.completion_day_level[&day];
+            (
+                completion.part2.as_ref().map_or(u64::MAX, |star| star.get_star_ts),
+                completion.part1.get_star_ts,
+                m.id,
+            )
+        });
+        let name_width = name_width(&members);
+
+        let mut table = String::new();
+        writeln!(
+            table,
+            "Rank | {:<name_width$} |       Part 1 |       Part 2 |        Delta",
+            "Name"
+        )
+        .unwrap();
+        writeln!(
+            table,
+            "-----+-{}-+--------------+--------------+-------------",
+            "-".repeat(name_width)
+        )
+        .unwrap();
+
+        let format =
+            |time: Option<Duration>| time.map(unlock::format_countdown).unwrap_or_default();
+        for (rank, member) in members.iter().enumerate() {
+            writeln!(
+                table,
+                "{:>4} | {:<name_width$} | {:>12} | {:>12} | {:>12}",
+                rank + 1,
+                member.display_name(),
+                format(member.solve_time(year, day, 1)),
+                format(member.solve_time(year, day, 2)),
+                format(member.delta(day))
+            )
+            .unwrap();
+        }
+
+        trim_lines(&table)
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Time from the unlock of the day until the star of the part was collected.
+    pub fn solve_time(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let star = self.star(day, part)?;
+        (UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+            .duration_since(unlock::unlock_time(year, day))
+            .ok()
+    }
+
+    /// Time between the first and the second star of a day.
+    pub fn delta(&self, day: u8) -> Option<Duration> {
+        let first = self.star(day, 1)?.get_star_ts;
+        let second = self.star(day, 2)?.get_star_ts;
+        Some(Duration::from_secs(second.saturating_sub(first)))
+    }
+
+    fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        let completion = self.completion_day_level.get(&day)?;
+        match part {
+            1 => Some(&completion.part1),
+            _ => completion.part2.as_ref(),
+        }
+    }
+}
+
+// Empty cells at the end of rows would leave trailing spaces
+fn trim_lines(table: &str) -> String {
+    table.lines().map(|line| format!("{}\n", line.trim_end())).collect()
+}
+
+fn name_width(members: &[&Member]) -> usize {
+    members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len())
+}
+
+/// Fetches a private leaderboard with the configuration from the environment.
+pub fn get_leaderboard(id: u64) -> Result<Leaderboard, InputError> {
+    get_leaderboard_with(&Config::from_env()?, id)
+}
+
+/// Fetches the private leaderboard `id` of the configured year, using the session cookie.
+pub fn get_leaderboard_with(config: &Config, id: u64) -> Result<Leaderboard, InputError> {
+    let session = config.session()?;
+    let url = format!(
+        "{}/{}/leaderboard/private/view/{}.json",
+        config.base_url, config.year, id
+    );
+    let (status, body) = input::send(config, true, |client| {
+        client
+            .get(&url)
+            .header("Cookie", format!("session={}", session))
+    })?;
+
+    match status {
+        // Without a valid session the server redirects to the login page, which is no JSON
+        StatusCode::OK => Leaderboard::parse(&body)
+            .map_err(|e| InputError::UnexpectedResponse(format!("invalid leaderboard: {}", e))),
+        status => Err(InputError::Status(status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    const LEADERBOARD: &str = include_str!("../test_input/fixtures/leaderboard.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.owner_id, 1234);
+        assert_eq!(leaderboard.year(), 2022);
+        assert_eq!(leaderboard.members.len(), 3);
+
+        let ranking: Vec<String> = leaderboard.ranking().iter().map(|m| m.display_name()).collect();
+        assert_eq!(ranking, ["Alice", "(anonymous user #5678)", "Carol"]);
+
+        let alice = &leaderboard.members["1234"];
+        assert_eq!(alice.completion_day_level[&1].part1.star_index, 2011);
+        assert_eq!(alice.solve_time(2022, 1, 1), Some(Duration::from_secs(305)));
+        assert_eq!(alice.solve_time(2022, 1, 2), Some(Duration::from_secs(590)));
+        assert_eq!(alice.delta(1), Some(Duration::from_secs(285)));
+
+        let anonymous = &leaderboard.members["5678"];
+        assert_eq!(anonymous.solve_time(2022, 1, 2), None);
+        assert_eq!(anonymous.delta(1), None);
+        assert_eq!(anonymous.delta(2), Some(Duration::from_secs(1200)));
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(
+            leaderboard.render(),
+            "\
+Rank | Name                   | Score | Stars | 1234567890123456789012345
+-----+------------------------+-------+-------+--------------------------
+   1 | Alice                  |    12 |     4 | **
+   2 | (anonymous user #5678) |     6 |     3 | .*
+   3 | Carol                  |     0 |     0 |
+"
+        );
+        assert_eq!(
+            leaderboard.render_day(1),
+            "\
+Rank | Name                   |       Part 1 |       Part 2 |        Delta
+-----+------------------------+--------------+--------------+-------------
+   1 | Alice                  |     00:05:05 |     00:09:50 |     00:04:45
+   2 | (anonymous user #5678) |     01:00:00 |              |
+"
+        );
+    }
+
+    #[test]
+    fn test_get_leaderboard() {
+        let server = TestServer::start(vec![
+            (200, LEADERBOARD.to_string()),
+            (200, "<html>Log in</html>".to_string()),
+        ]);
+        let config = Config {
+            session: Some("test-session".to_string()),
+            base_url: server.url.clone(),
+            min_interval: Duration::ZERO,
+            ..Config::default()
+        };
+
+        let leaderboard = get_leaderboard_with(&config, 1234).unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+        assert!(matches!(
+            get_leaderboard_with(&config, 1234),
+            Err(InputError::UnexpectedResponse(_))
+        ));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/2022/leaderboard/private/view/1234.json");
+        assert_eq!(requests[0].header("cookie"), Some("session=test-session"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Solutions for Advent of Code 2022. Every day lives in its own module and implements
+//! [`Solution`], the `aoc` binary runs them through [`DAYS`].
+
+pub mod answers;
+pub mod examples;
+pub mod input;
+pub mod interval;
+pub mod leaderboard;
+pub mod scaffold;
+mod solution;
+#[cfg(test)]
+mod test_rng;
+#[cfg(test)]
+mod test_server;
+pub mod unlock;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+
+pub use solution::{PartRun, Run, Solution};
+
+/// A registered day, both of its parts are solved.
+pub struct Day {
+    pub day: u8,
+    run: fn(&str, &[u8]) -> Run,
+}
+
+impl Day {
+    const fn new<S: Solution>(day: u8) -> Self {
+        Day {
+            day,
+            run: solution::run::<S>,
+        }
+    }
+
+    /// Parses the input and solves the given parts, see `Run`.
+    pub fn run(&self, input: &str, parts: &[u8]) -> Run {
+        (self.run)(input, parts)
+    }
+}
+
+/// Registry of all solved days, used by the `aoc` runner. Day 19 is not solved yet and is left
+/// out until it is.
+pub const DAYS: [Day; 18] = [
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day9::Day9>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+];
+
+/// Looks up a day in the registry.
+pub fn get_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,548 @@
+use advent22::{
+    answers::{Answers, Check, ANSWERS_FILE},
+    day5::{self, Crane, CrateMover9000, CrateMover9001, LimitedCrane},
+    examples,
+    get_day,
+    input::{self, InputSource, SubmitResult},
+    leaderboard, scaffold,
+    unlock,
+    Day, DAYS,
+};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
+
+const USAGE: &str = "Usage:
+    aoc run <day|all> [--part <1|2>] [--input <path> | -] [--check] [--wait]
+    aoc submit <day> <1|2> [answer]
+    aoc examples <day> [--block <n>] [--force]
+    aoc leaderboard <id> [--day <day>]
+    aoc new <day> [--title <title>]
+    aoc animate [--input <path> | -] [--crane <9000|9001> | --capacity <n>] [--delay <ms>]";
+
+struct RunOptions {
+    days: Vec<&'static Day>,
+    part: Option<u8>,
+    source: InputSource,
+    check: bool,
+    // Wait for the day to unlock before downloading its input
+    wait: bool,
+}
+
+struct SubmitOptions {
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+}
+
+struct ExamplesOptions {
+    day: u8,
+    // 1-based index of the `<pre><code>` block to save
+    block: usize,
+    force: bool,
+}
+
+struct LeaderboardOptions {
+    id: u64,
+    day: Option<u8>,
+}
+
+// Replays the rearrangement of day 5, printing the stacks after every step
+struct AnimateOptions {
+    source: InputSource,
+    crane: Box<dyn Crane>,
+    delay: Duration,
+}
+
+// Usage errors print the usage, failures while running a command only the message
+enum CliError {
+    Usage(String),
+    Failed(String),
+}
+
+struct Answer {
+    day: u8,
+    part: u8,
+    answer: String,
+    check: Option<Check>,
+    parse_time: Option<Duration>,
+    time: Option<Duration>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => parse_run_options(&args[1..])
+            .map_err(CliError::Usage)
+            .and_then(|options| run(&options).map_err(CliError::Failed)),
+        Some("submit") => parse_submit_options(&args[1..])
+            .map_err(CliError::Usage)
+            .and_then(|options| submit(options).map_err(CliError::Failed)),
+        Some("examples") => parse_examples_options(&args[1..])
+            .map_err(CliError::Usage)
+            .and_then(|options| save_example(&options).map_err(CliError::Failed)),
+        Some("leaderboard") => parse_leaderboard_options(&args[1..])
+            .map_err(CliError::Usage)
+            .and_then(|options| show_leaderboard(&options).map_err(CliError::Failed)),
+        Some("new") => parse_new_options(&args[1..])
+            .map_err(CliError::Usage)
+            .and_then(|(day, title)| new_day(day, &title).map_err(CliError::Failed)),
+        Some("animate") => parse_animate_options(&args[1..])
+            .map_err(CliError::Usage)
+            .and_then(|options| animate(&options).map_err(CliError::Failed)),
+        Some(command) => Err(CliError::Usage(format!("Unknown command: {}", command))),
+        None => Err(CliError::Usage("Missing command".to_string())),
+    };
+
+    match result {
+        Ok(()) => (),
+        Err(CliError::Usage(e)) => {
+            eprintln!("Error: {}\n{}", e, USAGE);
+            process::exit(2);
+        }
+        Err(CliError::Failed(e)) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions, String> {
+    let mut args = args.iter();
+
+    let days = match args.next().map(String::as_str) {
+        Some("all") => DAYS.iter().collect(),
+        Some(day) => {
+            let day = day
+                .parse::<u8>()
+                .map_err(|_| format!("Invalid day: {}", day))?;
+            vec![get_day(day).ok_or(format!("Day {} is not solved yet", day))?]
+        }
+        None => return Err("Missing day".to_string()),
+    };
+
+    let mut part = None;
+    let mut source = InputSource::Network;
+    let mut check = false;
+    let mut wait = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                part = match args.next().map(String::as_str) {
+                    Some("1") => Some(1),
+                    Some("2") => Some(2),
+                    _ => return Err("--part must be 1 or 2".to_string()),
+                }
+            }
+            "--input" => {
+                source = match args.next().map(String::as_str) {
+                    Some("-") => InputSource::Stdin,
+                    Some(path) => InputSource::File(PathBuf::from(path)),
+                    None => return Err("--input requires a path".to_string()),
+                }
+            }
+            "-" => source = InputSource::Stdin,
+            "--check" => check = true,
+            "--wait" => wait = true,
+            arg => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    if days.len() > 1 && source != InputSource::Network {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+    if wait && (days.len() > 1 || source != InputSource::Network) {
+        return Err("--wait can only be used when downloading the input of a single day".to_string());
+    }
+
+    Ok(RunOptions {
+        days,
+        part,
+        source,
+        check,
+        wait,
+    })
+}
+
+fn run(options: &RunOptions) -> Result<(), String> {
+    let recorded = match options.check {
+        true => Some(Answers::load(Path::new(ANSWERS_FILE)).map_err(|e| e.to_string())?),
+        false => None,
+    };
+    let mut answers = Vec::new();
+
+    for day in &options.days {
+        let parts = match options.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+
+        let mut results = Vec::new();
+        let mut parse_time = None;
+
+        let input = match options.wait {
+            true => {
+                let mut waited = false;
+                let input = input::get_input_when_unlocked(day.day, |left| {
+                    eprint!("\rDay {} unlocks in {}  ", day.day, unlock::format_countdown(left));
+                    waited = true;
+                });
+                if waited {
+                    eprintln!();
+                }
+                input
+            }
+            false => input::read_input(day.day, options.source.clone()),
+        };
+
+        match input {
+            Ok(input) => {
+                let run = day.run(&input, &parts);
+                parse_time = Some(run.parse_time);
+                for p in run.parts {
+                    let answer = p.answer.map_err(|e| format!("error: {}", e));
+                    results.push((p.part, (answer, Some(p.time))));
+                }
+            }
+            Err(e) => {
+                for part in parts {
+                    results.push((part, (Err(format!("error: {}", e)), None)));
+                }
+            }
+        }
+
+        for (i, (part, (answer, time))) in results.into_iter().enumerate() {
+            let check = recorded.as_ref().map(|recorded| match &answer {
+                Ok(answer) => recorded.check(day.day, part, answer),
+                Err(_) if recorded.get(day.day, part).is_some() => Check::Fail,
+                Err(_) => Check::Missing,
+            });
+
+            answers.push(Answer {
+                day: day.day,
+                part,
+                answer: answer.unwrap_or_else(|e| e),
+                check,
+                // The input is parsed once per day, its time is only shown in the first row
+                parse_time: if i == 0 { parse_time } else { None },
+                time,
+            });
+        }
+    }
+
+    print_answers(&answers);
+
+    if answers.iter().any(|a| a.check == Some(Check::Fail)) {
+        return Err("Some answers do not match the recorded ones".to_string());
+    }
+    Ok(())
+}
+
+fn parse_submit_options(args: &[String]) -> Result<SubmitOptions, String> {
+    let (day, part, answer) = match args {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer.clone())),
+        _ => return Err("submit expects a day, a part and optionally an answer".to_string()),
+    };
+
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid day: {}", day))?;
+    let part = match part.as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => return Err("The part must be 1 or 2".to_string()),
+    };
+
+    Ok(SubmitOptions { day, part, answer })
+}
+
+// Submits the given answer, or the answer of the solver for the downloaded input if none is given
+fn submit(options: SubmitOptions) -> Result<(), String> {
+    let SubmitOptions { day, part, answer } = options;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = get_day(day).ok_or(format!("Day {} is not solved yet", day))?;
+            let input = input::get_input(day.day).map_err(|e| e.to_string())?;
+            let run = day.run(&input, &[part]);
+            run.parts[0].answer.clone()?
+        }
+    };
+
+    println!("Submitting {} for day {} part {}", answer, day, part);
+    let result = input::submit_answer(day, part, &answer).map_err(|e| e.to_string())?;
+    println!("{}", result);
+
+    if result == SubmitResult::Correct {
+        let path = Path::new(ANSWERS_FILE);
+        let mut answers = Answers::load(path).map_err(|e| e.to_string())?;
+        answers.set(day, part, answer);
+        answers.save(path).map_err(|e| e.to_string())?;
+        println!("Recorded the answer in {}", ANSWERS_FILE);
+    }
+
+    Ok(())
+}
+
+fn parse_examples_options(args: &[String]) -> Result<ExamplesOptions, String> {
+    let mut args = args.iter();
+
+    let day = match args.next() {
+        Some(day) => day
+            .parse::<u8>()
+            .map_err(|_| format!("Invalid day: {}", day))?,
+        None => return Err("Missing day".to_string()),
+    };
+
+    let mut block = 1;
+    let mut force = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--block" => {
+                block = args
+                    .next()
+                    .and_then(|block| block.parse::<usize>().ok())
+                    .filter(|&block| block > 0)
+                    .ok_or("--block must be a positive number")?
+            }
+            "--force" => force = true,
+            arg => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(ExamplesOptions { day, block, force })
+}
+
+// Downloads the puzzle page and saves one of its example blocks, the first one by default
+fn save_example(options: &ExamplesOptions) -> Result<(), String> {
+    let path = examples::example_path(options.day);
+    if path.exists() && !options.force {
+        return Err(format!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    let page = input::get_puzzle_page(options.day).map_err(|e| e.to_string())?;
+    let blocks = examples::extract_examples(&page);
+
+    for (i, block) in blocks.iter().enumerate() {
+        let first_line = block.lines().next().unwrap_or_default();
+        println!("{:>3}: {} ({} lines)", i + 1, first_line, block.lines().count());
+    }
+
+    let example = blocks.get(options.block - 1).ok_or(format!(
+        "The puzzle page has {} example blocks, there is no block {}",
+        blocks.len(),
+        options.block
+    ))?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, example).map_err(|e| e.to_string())?;
+    println!("Saved block {} in {}", options.block, path.display());
+
+    Ok(())
+}
+
+fn parse_leaderboard_options(args: &[String]) -> Result<LeaderboardOptions, String> {
+    let mut args = args.iter();
+
+    let id = match args.next() {
+        Some(id) => id
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid leaderboard id: {}", id))?,
+        None => return Err("Missing leaderboard id".to_string()),
+    };
+
+    let mut day = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                day = Some(
+                    args.next()
+                        .and_then(|day| day.parse::<u8>().ok())
+                        .filter(|day| (1..=25).contains(day))
+                        .ok_or("--day must be between 1 and 25")?,
+                )
+            }
+            arg => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(LeaderboardOptions { id, day })
+}
+
+fn show_leaderboard(options: &LeaderboardOptions) -> Result<(), String> {
+    let leaderboard = leaderboard::get_leaderboard(options.id).map_err(|e| e.to_string())?;
+    match options.day {
+        Some(day) => print!("{}", leaderboard.render_day(day)),
+        None => print!("{}", leaderboard.render()),
+    }
+    Ok(())
+}
+
+fn parse_new_options(args: &[String]) -> Result<(u8, String), String> {
+    let (day, title) = match args {
+        [day] => (day, "TODO".to_string()),
+        [day, flag, title] if flag == "--title" => (day, title.clone()),
+        _ => return Err("new expects a day and optionally --title <title>".to_string()),
+    };
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid day: {}", day))?;
+
+    Ok((day, title))
+}
+
+// Run from the root of the crate, the new module is written to `src/dayN.rs`
+fn new_day(day: u8, title: &str) -> Result<(), String> {
+    let files = scaffold::create_day(Path::new("."), day, title).map_err(|e| e.to_string())?;
+    for file in files {
+        println!("Wrote {}", file.display());
+    }
+    Ok(())
+}
+
+fn parse_animate_options(args: &[String]) -> Result<AnimateOptions, String> {
+    let mut args = args.iter();
+
+    let mut source = InputSource::Network;
+    let mut crane: Option<Box<dyn Crane>> = None;
+    let mut delay = Duration::ZERO;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                source = match args.next().map(String::as_str) {
+                    Some("-") => InputSource::Stdin,
+                    Some(path) => InputSource::File(PathBuf::from(path)),
+                    None => return Err("--input requires a path".to_string()),
+                }
+            }
+            "-" => source = InputSource::Stdin,
+            "--crane" | "--capacity" if crane.is_some() => {
+                return Err("Only one of --crane and --capacity can be given".to_string())
+            }
+            "--crane" => {
+                crane = match args.next().map(String::as_str) {
+                    Some("9000") => Some(Box::new(CrateMover9000)),
+                    Some("9001") => Some(Box::new(CrateMover9001)),
+                    _ => return Err("--crane must be 9000 or 9001".to_string()),
+                }
+            }
+            "--capacity" => {
+                let capacity = args
+                    .next()
+                    .and_then(|capacity| capacity.parse::<usize>().ok())
+                    .and_then(LimitedCrane::new)
+                    .ok_or("--capacity must be a positive number")?;
+                crane = Some(Box::new(capacity))
+            }
+            "--delay" => {
+                delay = args
+                    .next()
+                    .and_then(|delay| delay.parse::<u64>().ok())
+                    .map(Duration::from_millis)
+                    .ok_or("--delay must be a number of milliseconds")?
+            }
+            arg => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(AnimateOptions {
+        source,
+        crane: crane.unwrap_or_else(|| Box::new(CrateMover9000)),
+        delay,
+    })
+}
+
+fn animate(options: &AnimateOptions) -> Result<(), String> {
+    let input = input::read_input(5, options.source.clone()).map_err(|e| e.to_string())?;
+    let (stacks, instructions) = day5::parse_input(&input).map_err(|e| e.to_string())?;
+
+    println!("{}\n", stacks);
+    let mut last = stacks.clone();
+    let steps = day5::replay(options.crane.as_ref(), &stacks, &instructions);
+    for (instruction, step) in instructions.iter().zip(steps) {
+        thread::sleep(options.delay);
+        last = step.map_err(|(step, e)| format!("Step {}: {}", step, e))?;
+        println!("{}\n\n{}\n", instruction, last);
+    }
+    println!("Top crates: {}", day5::get_keyword(&last));
+
+    Ok(())
+}
+
+fn print_answers(answers: &[Answer]) {
+    let answer_width = answers
+        .iter()
+        .flat_map(|a| a.answer.lines())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    let with_check = answers.iter().any(|a| a.check.is_some());
+
+    // The check column is placed before the answers, those can be arbitrarily long
+    let (check_header, check_separator, check_empty) = match with_check {
+        true => (" Check   |", "---------+", "         |"),
+        false => ("", "", ""),
+    };
+
+    println!("Day | Part |    Parse |     Time |{} Answer", check_header);
+    println!(
+        "----+------+----------+----------+{}-{}",
+        check_separator,
+        "-".repeat(answer_width)
+    );
+
+    for answer in answers {
+        let mut lines = answer.answer.lines();
+        let check = match answer.check {
+            Some(check) => format!(" {:<7} |", check.to_string()),
+            None => String::new(),
+        };
+        println!(
+            "{:>3} | {:>4} | {:>8} | {:>8} |{} {}",
+            answer.day,
+            answer.part,
+            answer.parse_time.map(format_duration).unwrap_or_default(),
+            answer.time.map(format_duration).unwrap_or_default(),
+            check,
+            lines.next().unwrap_or_default()
+        );
+        // Multi-line answers (like the CRT output of day 10) continue below their row
+        for line in lines {
+            println!("    |      |          |          |{} {}", check_empty, line);
+        }
+    }
+
+    let total: Duration = answers
+        .iter()
+        .flat_map(|a| [a.parse_time, a.time])
+        .flatten()
+        .sum();
+    println!("\nTotal time: {}", format_duration(total));
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
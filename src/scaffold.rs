@@ -0,0 +1,205 @@
+//! Generates the module of a new day from `templates/day.rs` and registers it in `lib.rs` and
+//! the benchmarks.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// Errors while creating a new day.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u8),
+    Exists(PathBuf),
+    /// A file the day is registered in does not have the expected shape.
+    Malformed(PathBuf, String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "Day {} is not between 1 and 25", day),
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Malformed(path, msg) => {
+                write!(f, "Could not register the day in {}: {}", path.display(), msg)
+            }
+            ScaffoldError::Io(e) => write!(f, "Could not write the new day: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(e: io::Error) -> Self {
+        ScaffoldError::Io(e)
+    }
+}
+
+/// Source of the module of a new day.
+pub fn render(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{title}", title)
+}
+
+/// Creates `src/dayN.rs` in the crate at `root` and registers the day in `src/lib.rs` and
+/// `benches/days.rs`. Returns the changed files.
+///
+/// Existing days are never overwritten, nothing is written if the day already exists.
+pub fn create_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+
+    let module = root.join("src").join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+
+    // Everything is prepared before writing, so a malformed file leaves no half-registered day
+    let lib = root.join("src").join("lib.rs");
+    let bench = root.join("benches").join("days.rs");
+    let new_lib = register_lib(&fs::read_to_string(&lib)?, day)
+        .map_err(|msg| ScaffoldError::Malformed(lib.clone(), msg))?;
+    let new_bench = register_bench(&fs::read_to_string(&bench)?, day)
+        .map_err(|msg| ScaffoldError::Malformed(bench.clone(), msg))?;
+
+    fs::write(&module, render(day, title))?;
+    fs::write(&lib, new_lib)?;
+    fs::write(&bench, new_bench)?;
+
+    Ok(vec![module, lib, bench])
+}
+
+// Adds the module declaration and the entry in `DAYS`
+fn register_lib(lib: &str, day: u8) -> Result<String, String> {
+    let lib = insert_line(lib, "pub mod day", day, &format!("pub mod day{};", day))?;
+    let lib = insert_line(
+        &lib,
+        "    Day::new::<day",
+        day,
+        &format!("    Day::new::<day{0}::Day{0}>({0}),", day),
+    )?;
+
+    // The length of the registry array is part of its type
+    let start = lib
+        .find("pub const DAYS: [Day; ")
+        .ok_or("DAYS not found")?
+        + "pub const DAYS: [Day; ".len();
+    let end = start + lib[start..].find(']').ok_or("DAYS not found")?;
+    let count: usize = lib[start..end]
+        .parse()
+        .map_err(|_| "the length of DAYS is not a number")?;
+
+    Ok(format!("{}{}{}", &lib[..start], count + 1, &lib[end..]))
+}
+
+fn register_bench(bench: &str, day: u8) -> Result<String, String> {
+    insert_line(
+        bench,
+        "    bench_day::<day",
+        day,
+        &format!("    bench_day::<day{0}::Day{0}>(c, &config, {0});", day),
+    )
+}
+
+// Inserts `line` into the block of lines starting with `prefix` followed by a day number,
+// keeping the block ordered by day.
+fn insert_line(source: &str, prefix: &str, day: u8, line: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let digits: String = line
+                .strip_prefix(prefix)?
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            Some((i, digits.parse().ok()?))
+        })
+        .collect();
+
+    if days.iter().any(|&(_, d)| d == day) {
+        return Err(format!("day {} is already registered", day));
+    }
+    let index = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => i,
+        None => days.last().ok_or(format!("no line starts with `{}`", prefix.trim()))?.0 + 1,
+    };
+    lines.insert(index, line);
+
+    let mut result = lines.join("\n");
+    if source.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_register_lib() {
+        let lib = "pub mod day1;\npub mod day3;\n\npub const DAYS: [Day; 2] = [\n    Day::new::<day1::Day1>(1),\n    Day::new::<day3::Day3>(3),\n];\n";
+
+        assert_eq!(
+            register_lib(lib, 2).unwrap(),
+            "pub mod day1;\npub mod day2;\npub mod day3;\n\npub const DAYS: [Day; 3] = [\n    Day::new::<day1::Day1>(1),\n    Day::new::<day2::Day2>(2),\n    Day::new::<day3::Day3>(3),\n];\n"
+        );
+        assert!(register_lib(lib, 4).unwrap().contains("pub mod day3;\npub mod day4;\n"));
+        assert!(register_lib(lib, 3).is_err());
+        assert!(register_lib("", 3).is_err());
+    }
+
+    #[test]
+    fn test_register_real_files() {
+        let lib = register_lib(include_str!("lib.rs"), 25).unwrap();
+        assert!(lib.contains(&format!("pub const DAYS: [Day; {}]", crate::DAYS.len() + 1)));
+        assert!(lib.contains("    Day::new::<day25::Day25>(25),\n];"));
+
+        let bench = register_bench(include_str!("../benches/days.rs"), 25).unwrap();
+        assert!(bench.contains("    bench_day::<day25::Day25>(c, &config, 25);\n}"));
+    }
+
+    #[test]
+    fn test_create_day() {
+        let root = env::temp_dir().join(format!("advent22-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("benches")).unwrap();
+        fs::write(root.join("src/lib.rs"), include_str!("lib.rs")).unwrap();
+        fs::write(root.join("benches/days.rs"), include_str!("../benches/days.rs")).unwrap();
+
+        create_day(&root, 25, "Test").unwrap();
+        let module = fs::read_to_string(root.join("src/day25.rs")).unwrap();
+        assert!(module.starts_with("//! Day 25: Test\n"));
+        assert!(module.contains("impl Solution for Day25 {"));
+
+        // The existing module is kept, even if it is not registered
+        fs::write(root.join("src/day25.rs"), "changed").unwrap();
+        assert!(matches!(
+            create_day(&root, 25, "Test"),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert_eq!(fs::read_to_string(root.join("src/day25.rs")).unwrap(), "changed");
+
+        // Registered days are refused as well
+        assert!(matches!(
+            create_day(&root, 1, "Test"),
+            Err(ScaffoldError::Malformed(_, _))
+        ));
+        assert!(matches!(
+            create_day(&root, 26, "Test"),
+            Err(ScaffoldError::InvalidDay(26))
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
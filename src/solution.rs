@@ -0,0 +1,148 @@
+use std::{
+    any::Any,
+    error::Error,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+/// Common interface of all days. `parse` prepares the puzzle input once, both parts work on
+/// the parsed input. Days that parse differently per part use the raw `&str` as their input.
+/// Invalid input is reported by `parse` as an error instead of a panic.
+pub trait Solution {
+    type Input<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn Error>>;
+    fn part1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(input: &Self::Input<'_>) -> Self::Part2;
+}
+
+/// Answers and wall times of running a day.
+#[derive(Debug)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Answer of a single part, or the message of the parse error or panic that aborted it.
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub time: Duration,
+}
+
+/// Parses the input once and solves the requested parts, timing every step.
+/// Parse errors and panics of the solution are reported as errors.
+pub fn run<S: Solution>(input: &str, parts: &[u8]) -> Run {
+    let start = Instant::now();
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| S::parse(input)));
+    let parse_time = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(e)) => Err(format!("parsing failed: {}", e)),
+        Err(e) => Err(format!("parsing panicked: {}", panic_message(&e))),
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            return Run {
+                parse_time,
+                parts: parts
+                    .iter()
+                    .map(|&part| PartRun {
+                        part,
+                        answer: Err(message.clone()),
+                        time: Duration::ZERO,
+                    })
+                    .collect(),
+            };
+        }
+    };
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| match part {
+                1 => S::part1(&parsed).to_string(),
+                _ => S::part2(&parsed).to_string(),
+            }))
+            .map_err(|e| format!("panicked: {}", panic_message(&e)));
+
+            PartRun {
+                part,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Run { parse_time, parts }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown error".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Input<'a> = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn Error>> {
+            assert!(!input.is_empty(), "Empty input");
+            Ok(input.lines().map(|line| line.parse()).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Self::Input<'_>) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(_input: &Self::Input<'_>) -> u32 {
+            panic!("Not implemented")
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let run = run::<Numbers>("1\n2\n3", &[1, 2]);
+        assert_eq!(run.parts.len(), 2);
+        assert_eq!(run.parts[0].answer, Ok("6".to_string()));
+        assert_eq!(
+            run.parts[1].answer,
+            Err("panicked: Not implemented".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_parse_error() {
+        let run = run::<Numbers>("1\nx", &[1, 2]);
+        assert_eq!(run.parts.len(), 2);
+        assert_eq!(
+            run.parts[0].answer,
+            Err("parsing failed: invalid digit found in string".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_parse_panic() {
+        let run = run::<Numbers>("", &[1]);
+        assert!(run.parts[0].answer.as_ref().unwrap_err().starts_with("parsing panicked"));
+    }
+}
@@ -0,0 +1,16 @@
+// Deterministic random numbers for tests that compare against a naive implementation, a linear
+// congruential generator with the constants of Knuth's MMIX.
+
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    // A number below `n`
+    pub fn below(&mut self, n: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize % n
+    }
+}
@@ -0,0 +1,162 @@
+//! Unlock times of the puzzles. A puzzle unlocks at midnight EST (UTC-5) on its day in December.
+
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Source of the current time, replaced in tests to avoid waiting in real time.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system time and `thread::sleep`.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Time at which the puzzle of a day unlocks, 05:00 UTC on December `day` of `year`.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    let seconds = days * 86_400 + 5 * 3600;
+    UNIX_EPOCH + Duration::from_secs(seconds as u64)
+}
+
+/// Time left until the puzzle of a day unlocks, `None` if it is already unlocked.
+pub fn time_until_unlock(clock: &impl Clock, year: u16, day: u8) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(clock.now())
+        .ok()
+        .filter(|left| !left.is_zero())
+}
+
+/// Sleeps until the puzzle of a day unlocks. `on_tick` is called with the time left about
+/// once per second, for showing a countdown.
+pub fn wait_for_unlock(clock: &impl Clock, year: u16, day: u8, mut on_tick: impl FnMut(Duration)) {
+    while let Some(left) = time_until_unlock(clock, year, day) {
+        on_tick(left);
+        // Sleep to the next full second of the countdown
+        let step = match left.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos as u64),
+        };
+        clock.sleep(step.min(left));
+    }
+}
+
+/// Formats the time left as `1d 02:03:04`, or `02:03:04` below a day.
+pub fn format_countdown(left: Duration) -> String {
+    let seconds = left.as_secs();
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    match seconds / 86_400 {
+        0 => time,
+        days => format!("{}d {}", days, time),
+    }
+}
+
+// Days since 1970-01-01 of a date in the proleptic Gregorian calendar, from
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// A clock that only moves when slept on.
+    pub struct FakeClock {
+        pub now: Cell<SystemTime>,
+        pub sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        pub fn new(now: SystemTime) -> FakeClock {
+            FakeClock {
+                now: Cell::new(now),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2022, 1),
+            UNIX_EPOCH + Duration::from_secs(1_669_870_800)
+        );
+        // 2022-12-25T05:00:00Z
+        assert_eq!(
+            unlock_time(2022, 25),
+            UNIX_EPOCH + Duration::from_secs(1_671_944_400)
+        );
+        // 2015-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2015, 1),
+            UNIX_EPOCH + Duration::from_secs(1_448_946_000)
+        );
+    }
+
+    #[test]
+    fn test_wait_for_unlock() {
+        let unlock = unlock_time(2022, 3);
+        let clock = FakeClock::new(unlock - Duration::from_millis(2500));
+
+        let mut ticks = Vec::new();
+        wait_for_unlock(&clock, 2022, 3, |left| ticks.push(left));
+
+        assert_eq!(clock.now(), unlock);
+        assert_eq!(
+            ticks,
+            vec![
+                Duration::from_millis(2500),
+                Duration::from_secs(2),
+                Duration::from_secs(1)
+            ]
+        );
+        assert_eq!(time_until_unlock(&clock, 2022, 3), None);
+
+        // Already unlocked days do not wait at all
+        wait_for_unlock(&clock, 2022, 2, |_| panic!("should not wait"));
+        assert_eq!(clock.sleeps.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(59_900)), "00:00:59");
+        assert_eq!(format_countdown(Duration::from_secs(3 * 3600 + 62)), "03:01:02");
+        assert_eq!(format_countdown(Duration::from_secs(2 * 86_400 + 5)), "2d 00:00:05");
+    }
+}
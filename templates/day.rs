@@ -0,0 +1,40 @@
+//! Day {day}: {title}
+
+use crate::Solution;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Box<dyn std::error::Error>> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        input.len()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        input.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(Day{day}::part1(&Day{day}::parse(INPUT).unwrap()), 0);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(Day{day}::part2(&Day{day}::parse(INPUT).unwrap()), 0);
+    }
+}